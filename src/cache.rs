@@ -0,0 +1,50 @@
+// src/cache.rs
+use chrono::{DateTime, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::data_types::TableData;
+
+const CACHE_DIR: &str = "score_viewer";
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    fetched_at: i64,  // Unix timestamp of the successful fetch
+    data: TableData,
+}
+
+// Best effort, a failed write only means no cache after a restart
+pub fn store(key: &str, data: &TableData) {
+    if let Some(path) = cache_path(key) {
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let entry = CacheEntry {
+            fetched_at: Local::now().timestamp(),
+            data: data.clone(),
+        };
+        if let Ok(json) = serde_json::to_string(&entry) {
+            let _ = fs::write(path, json);
+        }
+    }
+}
+
+pub fn load(key: &str) -> Option<(TableData, DateTime<Local>)> {
+    let json = fs::read_to_string(cache_path(key)?).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&json).ok()?;
+    let fetched_at = Local.timestamp_opt(entry.fetched_at, 0).single()?;
+    
+    // Column types aren't stored, so work them out again
+    let mut data = entry.data;
+    data.infer_column_types();
+    Some((data, fetched_at))
+}
+
+fn cache_path(key: &str) -> Option<PathBuf> {
+    // Keep the file name safe whatever the sheet is called
+    let file_name: String = key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    Some(dirs::cache_dir()?.join(CACHE_DIR).join(format!("{}.json", file_name)))
+}
@@ -0,0 +1,192 @@
+// src/cli.rs
+use std::error::Error;
+use std::path::PathBuf;
+
+use crate::cloud_handler::CloudHandler;
+use crate::columns::ColumnConfig;
+use crate::config::AppConfig;
+use crate::csv_handler::CSVHandler;
+use crate::data_types::TableData;
+use crate::url_handler::{UrlFetch, UrlHandler, Validators};
+use crate::xlsx_handler::XlsxHandler;
+
+pub const USAGE: &str = "\
+Usage: score_viewer [--size WxH] [--position X,Y] [--maximized] [--fullscreen] [--demo]
+       score_viewer --export OUT.csv (--file IN | --url URL) [--sheet NAME] [--all-columns]
+
+Without --export the viewer window opens as usual.
+
+  --size WxH      Initial window size, e.g. 1920x1080
+  --position X,Y  Initial window position
+  --maximized     Open the window maximized
+  --fullscreen    Open the window fullscreen
+  --demo          Show built-in sample results, no sheet or network needed
+
+  --export PATH   Write the processed table to PATH as CSV and exit
+  --file PATH     Read a local .csv or .xlsx file
+  --url URL       Fetch a Google Sheet or a plain CSV URL
+  --sheet NAME    Sheet tab to fetch from a Google Sheet, or worksheet to read
+                  from an .xlsx file instead of the first one
+  --all-columns   Also export the columns columns.toml hides
+  --help          Show this help
+
+Set SVR_LOG to a level such as debug, and SVR_LOG_FILE to log to a file.";
+
+// Exit codes for scripts
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILED: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+
+#[derive(Debug, Default)]
+pub struct ExportArgs {
+    pub output: PathBuf,
+    pub file: Option<PathBuf>,
+    pub url: Option<String>,
+    pub sheet: String,
+    pub all_columns: bool,
+}
+
+// Overrides for this launch, on top of the persisted config
+#[derive(Debug, Default)]
+pub struct WindowArgs {
+    pub size: Option<(u32, u32)>,
+    pub position: Option<(i32, i32)>,
+    pub maximized: bool,
+    pub fullscreen: bool,
+    pub demo: bool,
+}
+
+impl WindowArgs {
+    pub fn size(&self, config: &AppConfig) -> (u32, u32) {
+        self.size.unwrap_or_else(|| config.window_size())
+    }
+
+    pub fn position(&self, config: &AppConfig) -> Option<(i32, i32)> {
+        self.position.or_else(|| config.window_position())
+    }
+
+    // Whether this launch places the window itself, so its geometry isn't remembered
+    pub fn places_window(&self) -> bool {
+        self.size.is_some() || self.position.is_some()
+    }
+}
+
+pub enum Command {
+    Gui(WindowArgs),
+    Help,
+    Export(ExportArgs),
+}
+
+pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut output = None;
+    let mut file = None;
+    let mut url = None;
+    let mut sheet = String::new();
+    let mut all_columns = false;
+    let mut window = WindowArgs::default();
+    
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--export" => output = Some(PathBuf::from(value()?)),
+            "--file" => file = Some(PathBuf::from(value()?)),
+            "--url" => url = Some(value()?),
+            "--sheet" => sheet = value()?,
+            "--all-columns" => all_columns = true,
+            "--size" => window.size = Some(parse_pair(&value()?, 'x').ok_or("--size expects WIDTHxHEIGHT")?),
+            "--position" => window.position = Some(parse_pair(&value()?, ',').ok_or("--position expects X,Y")?),
+            "--maximized" => window.maximized = true,
+            "--fullscreen" => window.fullscreen = true,
+            "--demo" => window.demo = true,
+            "--help" | "-h" => return Ok(Command::Help),
+            other => return Err(format!("unknown argument: {}", other)),
+        }
+    }
+    
+    let output = match output {
+        Some(output) => output,
+        None if file.is_none() && url.is_none() && !all_columns => return Ok(Command::Gui(window)),
+        None => return Err("--file, --url and --all-columns are only used with --export".to_string()),
+    };
+    
+    if file.is_some() == url.is_some() {
+        return Err("--export needs exactly one of --file or --url".to_string());
+    }
+    
+    Ok(Command::Export(ExportArgs { output, file, url, sheet, all_columns }))
+}
+
+fn parse_pair<T: std::str::FromStr>(value: &str, separator: char) -> Option<(T, T)> {
+    let (first, second) = value.split_once(separator)?;
+    Some((first.trim().parse().ok()?, second.trim().parse().ok()?))
+}
+
+// Fetch, process and write the table without opening a window, returning the exit code
+pub fn run_export(args: ExportArgs, config: &AppConfig) -> i32 {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return EXIT_FAILED;
+        }
+    };
+    
+    let result = runtime.block_on(load(&args, config))
+        .and_then(|data| {
+            // Hidden columns are left out, as on screen, unless asked for
+            let data = if args.all_columns { data } else { data.visible_view() };
+            CSVHandler::write_csv(&args.output, &data)?;
+            Ok(data)
+        });
+    
+    match result {
+        Ok(data) => {
+            eprintln!("Wrote {} rows to {}", data.rows.len(), args.output.display());
+            EXIT_OK
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            EXIT_FAILED
+        }
+    }
+}
+
+// Same handlers and column settings as the viewer, so the output matches the screen
+async fn load(args: &ExportArgs, config: &AppConfig) -> Result<TableData, Box<dyn Error>> {
+    let columns = ColumnConfig::load();
+    
+    if let Some(ref path) = args.file {
+        let is_xlsx = path.extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("xlsx"));
+        
+        return if is_xlsx {
+            let mut xlsx_handler = XlsxHandler::new(columns);
+            if !args.sheet.is_empty() {
+                xlsx_handler = xlsx_handler.with_sheet(args.sheet.clone());
+            }
+            Ok(xlsx_handler.read_xlsx(path.clone()).await?)
+        } else {
+            Ok(CSVHandler::new(columns).read_csv(path.clone()).await?)
+        };
+    }
+    
+    let url = args.url.as_deref().unwrap_or_default();
+    
+    // Bare spreadsheet IDs and Google links go through the Sheets handler
+    let is_google_sheet = !url.contains('/') || url.contains("docs.google.com");
+    
+    if is_google_sheet {
+        let mut cloud_handler = CloudHandler::new(columns).with_timeout(config.request_timeout());
+        if let Some(path) = config.credentials_path() {
+            cloud_handler = cloud_handler.with_credentials(path);
+        }
+        cloud_handler.fetch_data(url, &args.sheet).await
+    } else {
+        let url_handler = UrlHandler::new(columns).with_timeout(config.request_timeout());
+        match url_handler.fetch(url, &Validators::default()).await? {
+            UrlFetch::Updated(data, _) => Ok(data),
+            UrlFetch::NotModified => Err("server returned no data".into()),
+        }
+    }
+}
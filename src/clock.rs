@@ -0,0 +1,27 @@
+// src/clock.rs
+use chrono::{NaiveTime, Timelike};
+
+// Parse a countdown target such as "14:30" or "14:30:15", in local time
+pub fn parse_target(value: &str) -> Option<NaiveTime> {
+    let value = value.trim();
+    NaiveTime::parse_from_str(value, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M"))
+        .ok()
+}
+
+// Whole seconds left until the target today, None once it has passed
+pub fn remaining_secs(now: NaiveTime, target: NaiveTime) -> Option<u32> {
+    let now = now.num_seconds_from_midnight();
+    let target = target.num_seconds_from_midnight();
+    (target > now).then(|| target - now)
+}
+
+// "4:05" below an hour, "1:04:05" above
+pub fn format_remaining(secs: u32) -> String {
+    let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{}:{:02}", minutes, secs)
+    }
+}
@@ -1,44 +1,225 @@
 // src/cloud_handler.rs
+use async_trait::async_trait;
 use google_sheets4::{api::ValueRange, Sheets};
 use std::error::Error;
+use std::fmt;
 use tokio::task;
 use yup_oauth2::{ServiceAccountAuthenticator, ServiceAccountKey};
 use serde_json::from_str;
 use std::fs::File;
-use std::io::Read;
+use std::io::{ErrorKind, Read};
+use std::path::PathBuf;
+use std::time::Duration;
+use chrono::{DateTime, Local};
 
+use crate::cache;
+use crate::columns::ColumnConfig;
+use crate::csv_handler::CSVHandler;
 use crate::data_types::TableData;
 
+const DEFAULT_CREDENTIALS: &str = "credentials.json";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_ATTEMPTS: u32 = 3;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+// Spreadsheet IDs are long; anything shorter is probably a typo rather than an ID
+const MIN_ID_LENGTH: usize = 20;
+const PUBLISHED_PREFIX: &str = "2PACX-";
+
+// Why a fetch failed, with quota errors kept apart so polling can back off
+#[derive(Debug, Clone)]
+pub enum FetchError {
+    RateLimited(Option<Duration>),  // HTTP 429, with the server's Retry-After if given
+    Failed(String),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::RateLimited(_) => write!(f, "Google Sheets read quota exceeded"),
+            FetchError::Failed(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+impl From<Box<dyn Error>> for FetchError {
+    fn from(err: Box<dyn Error>) -> Self {
+        match err.downcast_ref::<FetchError>() {
+            Some(err) => err.clone(),
+            None => FetchError::Failed(err.to_string()),
+        }
+    }
+}
+
+// Where the viewer gets Google Sheets data from, so a canned source can stand in
+// for the real API in demos
+#[async_trait]
+pub trait CloudClient: Send + Sync {
+    async fn fetch_data(&self, spreadsheet_url: &str, sheet_name: &str) -> Result<TableData, FetchError>;
+    async fn list_sheets(&self, spreadsheet_url: &str) -> Result<Vec<String>, FetchError>;
+    
+    // Fetch without touching the on-disk cache, for trying settings out
+    async fn fetch_uncached(&self, spreadsheet_url: &str, sheet_name: &str) -> Result<TableData, FetchError> {
+        self.fetch_data(spreadsheet_url, sheet_name).await
+    }
+}
+
 pub struct CloudHandler {
-    credentials_path: String,
+    credentials_path: PathBuf,
+    columns: ColumnConfig,
+    timeout: Duration,
 }
 
 impl CloudHandler {
-    pub fn new() -> Self {
+    pub fn new(columns: ColumnConfig) -> Self {
         CloudHandler {
-            credentials_path: "credentials.json".to_string(),
+            credentials_path: PathBuf::from(DEFAULT_CREDENTIALS),
+            columns,
+            timeout: DEFAULT_TIMEOUT,
         }
     }
 
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_credentials(mut self, path: PathBuf) -> Self {
+        self.credentials_path = path;
+        self
+    }
+
     pub async fn fetch_data(&self, spreadsheet_url: &str, sheet_name: &str) -> Result<TableData, Box<dyn Error>> {
-        let spreadsheet_id = self.extract_spreadsheet_id(spreadsheet_url)?;
-        let sheet = if sheet_name.is_empty() { "Sheet1" } else { sheet_name };
+        let data = self.fetch_uncached(spreadsheet_url, sheet_name).await?;
+        log::info!("Fetched {} rows from sheet '{}'", data.rows.len(), sheet_name);
+        
+        // Keep a copy on disk so a restart during an outage still has standings
+        cache::store(&Self::cache_key(spreadsheet_url, sheet_name)?, &data);
+        
+        Ok(data)
+    }
+
+    // The last successfully fetched data and when it was fetched
+    pub fn cached_data(spreadsheet_url: &str, sheet_name: &str) -> Option<(TableData, DateTime<Local>)> {
+        cache::load(&Self::cache_key(spreadsheet_url, sheet_name).ok()?)
+    }
+
+    fn cache_key(spreadsheet_url: &str, sheet_name: &str) -> Result<String, Box<dyn Error>> {
+        let spreadsheet_id = Self::extract_spreadsheet_id(spreadsheet_url)?;
+        Ok(format!("{}_{}", spreadsheet_id, sheet_name))
+    }
+
+    // The same fetch and parse as fetch_data, leaving the cache alone
+    pub async fn fetch_uncached(&self, spreadsheet_url: &str, sheet_name: &str) -> Result<TableData, Box<dyn Error>> {
+        let spreadsheet_id = Self::extract_spreadsheet_id(spreadsheet_url)?;
+        
+        // Without a service account, fall back to the public CSV export.
+        // Published IDs aren't real spreadsheet IDs, so the API can't use them.
+        if !self.credentials_path.exists() || Self::is_published_id(spreadsheet_id) {
+            log::debug!("Using the public CSV export for {}", spreadsheet_id);
+            return self.fetch_public(spreadsheet_id, sheet_name).await;
+        }
         
         // Authenticate with Google Sheets API
         let sheets = self.authenticate().await?;
         
         // Fetch data from Google Sheets
-        let range = format!("{}!A:Z", sheet);
-        let response = sheets.spreadsheets().values_get(spreadsheet_id, &range).await?;
+        let range = sheet_range(sheet_name);
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 1;
+        
+        // Retry transient failures with exponential backoff
+        let response = loop {
+            match sheets.spreadsheets().values_get(spreadsheet_id, &range).await {
+                Ok(response) => break response,
+                // Retrying straight away would only use up more quota
+                Err(google_sheets4::Error::Failure(response)) if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                    return Err(FetchError::RateLimited(retry_after(response.headers())).into());
+                }
+                // A missing sheet or a denied permission won't fix itself
+                Err(err) if attempt >= MAX_ATTEMPTS || !is_transient(&err) => return Err(err.into()),
+                Err(err) => {
+                    log::warn!("Sheets API attempt {} failed, retrying: {}", attempt, err);
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+            }
+        };
         
         // Process the data
         self.process_data(response).await
     }
 
+    // Titles of the worksheets in a spreadsheet, which needs the service account
+    pub async fn list_sheets(&self, spreadsheet_url: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        let spreadsheet_id = Self::extract_spreadsheet_id(spreadsheet_url)?;
+        if !self.credentials_path.exists() || Self::is_published_id(spreadsheet_id) {
+            return Err("listing sheets needs a service account".into());
+        }
+        
+        let sheets = self.authenticate().await?;
+        let (_, spreadsheet) = sheets.spreadsheets().get(spreadsheet_id).doit().await?;
+        
+        Ok(spreadsheet.sheets
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|sheet| sheet.properties.and_then(|properties| properties.title))
+            .collect())
+    }
+
+    async fn fetch_public(&self, spreadsheet_id: &str, sheet_name: &str) -> Result<TableData, Box<dyn Error>> {
+        let url = Self::public_export_url(spreadsheet_id, sheet_name)?;
+        let response = self.http_client()?
+            .get(url)
+            .send()
+            .await?;
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(FetchError::RateLimited(retry_after(response.headers())).into());
+        }
+        let response = response.error_for_status()?;
+        
+        // Private sheets redirect to a Google sign-in page instead of CSV
+        let is_html = response.headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map_or(false, |value| value.contains("text/html"));
+        if is_html {
+            log::warn!("Public export of {} returned a sign-in page", spreadsheet_id);
+            return Err("sheet is not publicly shared".into());
+        }
+        
+        let bytes = response.bytes().await?;
+        Ok(CSVHandler::parse_bytes(&bytes, &self.columns)?)
+    }
+
+    fn public_export_url(spreadsheet_id: &str, sheet_name: &str) -> Result<reqwest::Url, Box<dyn Error>> {
+        // Published-to-web sheets only offer the pub export
+        let (base, mut params) = if Self::is_published_id(spreadsheet_id) {
+            (format!("https://docs.google.com/spreadsheets/d/e/{}/pub", spreadsheet_id), vec![("output", "csv")])
+        } else {
+            (format!("https://docs.google.com/spreadsheets/d/{}/gviz/tq", spreadsheet_id), vec![("tqx", "out:csv")])
+        };
+        if !sheet_name.is_empty() {
+            params.push(("sheet", sheet_name));
+        }
+        Ok(reqwest::Url::parse_with_params(&base, &params)?)
+    }
+
     async fn authenticate(&self) -> Result<Sheets, Box<dyn Error>> {
         // Load service account key from file
+        log::info!("Authenticating with {}", self.credentials_path.display());
         let mut json = String::new();
-        File::open(&self.credentials_path)?.read_to_string(&mut json)?;
+        let mut file = File::open(&self.credentials_path).map_err(|err| -> Box<dyn Error> {
+            if err.kind() == ErrorKind::NotFound {
+                format!("credentials file not found at {}", self.credentials_path.display()).into()
+            } else {
+                err.into()
+            }
+        })?;
+        file.read_to_string(&mut json)?;
         
         let service_account_key: ServiceAccountKey = from_str(&json)?;
         
@@ -48,138 +229,444 @@ impl CloudHandler {
             .await?;
         
         // Create an authenticated Sheets client
-        let sheets = Sheets::new(
-            reqwest::Client::builder()
-                .build()?,
-            auth,
-        );
+        let sheets = Sheets::new(self.http_client()?, auth);
         
         Ok(sheets)
     }
 
-    fn extract_spreadsheet_id(&self, url: &str) -> Result<&str, Box<dyn Error>> {
-        // Extract spreadsheet ID from URL
-        // URLs typically look like: https://docs.google.com/spreadsheets/d/[SPREADSHEET_ID]/edit
+    fn http_client(&self) -> Result<reqwest::Client, Box<dyn Error>> {
+        // Never let a stalled connection hang the refresh
+        Ok(reqwest::Client::builder()
+            .connect_timeout(self.timeout)
+            .timeout(self.timeout)
+            .build()?)
+    }
+
+    // Accepts ".../d/<id>/edit" and ".../d/e/<id>/pubhtml" URLs as well as a bare ID
+    pub fn extract_spreadsheet_id(url: &str) -> Result<&str, Box<dyn Error>> {
+        let url = url.trim();
+        
+        // A bare ID has no slashes and only URL-safe characters
+        if url.len() >= MIN_ID_LENGTH && url.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Ok(url);
+        }
+        
         let parts: Vec<&str> = url.split('/').collect();
         
         for (i, part) in parts.iter().enumerate() {
-            if *part == "d" && i + 1 < parts.len() {
-                return Ok(parts[i+1]);
+            if *part == "d" {
+                // Published sheets have an extra "e" segment before the ID
+                let id = match parts.get(i + 1) {
+                    Some(&"e") => parts.get(i + 2),
+                    other => other,
+                };
+                if let Some(id) = id.filter(|id| !id.is_empty()) {
+                    return Ok(id);
+                }
             }
         }
         
-        Err("Invalid spreadsheet URL".into())
+        Err("Couldn't find a spreadsheet ID in that URL".into())
+    }
+
+    fn is_published_id(spreadsheet_id: &str) -> bool {
+        spreadsheet_id.starts_with(PUBLISHED_PREFIX)
     }
 
     async fn process_data(&self, response: ValueRange) -> Result<TableData, Box<dyn Error>> {
-        // Process the data from Google Sheets
-        task::spawn_blocking(move || {
-            let mut data = TableData::empty();
-            
-            if let Some(values) = response.values {
-                if values.is_empty() {
-                    return data;
-                }
-                
-                // Find the first row where the first cell contains "category" (case-insensitive)
-                let mut start_index = 0;
-                for (i, row) in values.iter().enumerate() {
-                    if !row.is_empty() && row[0].to_string().to_lowercase() == "category" {
-                        start_index = i;
-                        break;
-                    }
-                }
-                
-                // Extract data from the category row onward
-                let relevant_data = &values[start_index..];
-                if relevant_data.is_empty() {
-                    return data;
-                }
-                
-                // Columns to hide
-                let columns_to_hide = vec![
-                    "sport_id", "team_members", "team_name",
-                    "info", "result_code", "position_pre"
-                ];
-                
-                // Process headers
-                let headers = &relevant_data[0];
-                let mut visible_columns = Vec::new();
-                let mut processed_headers = Vec::new();
-                
-                for (i, header) in headers.iter().enumerate() {
-                    let header_str = header.to_string();
-                    
-                    // Check if this column should be hidden
-                    let should_hide = columns_to_hide.iter()
-                        .any(|col| header_str.to_lowercase().contains(col));
-                    
-                    visible_columns.push(!should_hide);
-                    
-                    if !should_hide {
-                        // Apply header replacements
-                        let processed_header = Self::replace_header(&header_str);
-                        processed_headers.push(processed_header);
-                    }
-                }
-                
-                data.headers = processed_headers;
-                
-                // Process rows
-                for row in relevant_data.iter().skip(1) {
-                    // Skip empty rows
-                    if row.is_empty() || row.iter().all(|cell| cell.as_str().map_or(true, |s| s.trim().is_empty())) {
-                        continue;
-                    }
-                    
-                    // Filter visible columns
-                    let mut filtered_row = Vec::new();
-                    for (i, cell) in row.iter().enumerate() {
-                        if i < visible_columns.len() && visible_columns[i] {
-                            filtered_row.push(cell.to_string());
-                        }
-                    }
-                    
-                    data.rows.push(filtered_row);
-                }
-            }
-            
-            data
-        }).await.unwrap_or_else(|_| TableData::empty())
-    }
-
-    fn replace_header(header: &str) -> String {
-        let header_lower = header.to_lowercase();
-        
-        // Header replacements mapping
-        let replacements = [
-            ("category", "Series"),
-            ("first_name", "Name"),
-            ("last_name", "Surname"),
-            ("organization", "Club"),
-            ("napat", "X"),
-            ("result", "Result"),
-            ("posit.", "Rank")
-        ];
-        
-        // First check for part-X and psum-X patterns
-        if header_lower.contains("part-") {
-            if let Some(part_num) = header.split('-').nth(1) {
-                return format!("S{}", part_num);
-            }
-        } else if header_lower.contains("psum-") {
-            if let Some(part_num) = header.split('-').nth(1) {
-                return format!("P{}", part_num);
-            }
+        // Cells arrive as JSON values; keep strings as they are and render the rest
+        let values: Vec<Vec<String>> = response.values.unwrap_or_default()
+            .into_iter()
+            .map(|row| row.into_iter()
+                .map(|cell| match cell {
+                    serde_json::Value::String(text) => text,
+                    serde_json::Value::Null => String::new(),
+                    other => other.to_string(),
+                })
+                .collect())
+            .collect();
+        let columns = self.columns.clone();
+        
+        Ok(task::spawn_blocking(move || process_values(values, &columns)).await?)
+    }
+}
+
+// A range of just the quoted sheet name covers every filled column, so wide
+// sheets with 30 stages aren't cut off at Z
+fn sheet_range(sheet_name: &str) -> String {
+    let sheet = if sheet_name.is_empty() { "Sheet1" } else { sheet_name };
+    format!("'{}'", sheet.replace('\'', "''"))
+}
+
+// Network trouble and server-side errors, which may pass on a retry
+fn is_transient(err: &google_sheets4::Error) -> bool {
+    match err {
+        google_sheets4::Error::HttpError(_) | google_sheets4::Error::Io(_) => true,
+        google_sheets4::Error::Failure(response) => response.status().is_server_error(),
+        _ => false,
+    }
+}
+
+// Seconds to wait from a Retry-After header; the HTTP-date form isn't used by Google
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers.get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+// Names from a comma-separated sheet field that the spreadsheet doesn't have.
+// Sheets matches tab names case-insensitively, so the check does too.
+pub fn unknown_sheets<'a>(input: &'a str, available: &[String]) -> Vec<&'a str> {
+    input.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .filter(|name| !available.iter().any(|sheet| sheet.trim().to_lowercase() == name.to_lowercase()))
+        .collect()
+}
+
+// Turn the sheet's cells into a table: skip the title block above the header
+// marker, hide and rename columns, and drop empty rows
+pub fn process_values(values: Vec<Vec<String>>, columns: &ColumnConfig) -> TableData {
+    let mut data = TableData::empty();
+    
+    if values.is_empty() {
+        return data;
+    }
+    
+    // Find the first row whose first cell is a header marker such as "category",
+    // falling back to the first row
+    let start_index = values.iter()
+        .position(|row| row.first().map_or(false, |cell| columns.is_header_marker(cell)))
+        .unwrap_or(0);
+    
+    // Rows above the header carry the event name and date
+    data.set_preamble(values[..start_index].iter()
+        .map(|row| row.iter().map(String::as_str)));
+    
+    // Mappings in the preamble rename columns for this sheet only
+    let columns = &columns.with_sheet_replacements(values[..start_index].iter()
+        .flat_map(|row| row.iter().map(String::as_str)));
+    
+    // Extract data from the category row onward
+    let relevant_data = &values[start_index..];
+    
+    // Process headers the same way as CSV files, duplicates included
+    let (processed_headers, visible_columns) = CSVHandler::process_headers(relevant_data[0].clone(), columns);
+    data.headers = processed_headers;
+    data.visible = visible_columns;
+    
+    // Process rows
+    let mut trimmed_rows = 0;
+    for row in relevant_data.iter().skip(1) {
+        // Skip empty rows
+        if row.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
         }
         
-        // Then check other replacements
-        for (original, replacement) in replacements.iter() {
-            if header_lower.contains(original) {
-                return replacement.to_string();
-            }
+        // Stray cells past the header have no column to go in
+        let width = data.headers.len();
+        if row.len() > width && row[width..].iter().any(|cell| !cell.trim().is_empty()) {
+            trimmed_rows += 1;
         }
         
-        header.to_string()
+        // Hidden cells are kept for coloring rules and export
+        data.rows.push(row.iter().take(width).cloned().collect());
+    }
+    
+    // Short rows are padded to the header length, as for CSV files
+    columns.repair_rows(&mut data);
+    if trimmed_rows > 0 {
+        log::info!("Ignored cells past the last header column in {} rows", trimmed_rows);
+    }
+    if data.repaired_rows > 0 {
+        log::info!("Padded {} short rows to the header length", data.repaired_rows);
+    }
+    
+    columns.normalize_cells(&mut data);
+    columns.strip_totals(&mut data);
+    columns.apply_order(&mut data);
+    data.key_columns = columns.key_columns.clone();
+    data.infer_column_types();
+    
+    data
+}
+
+#[async_trait]
+impl CloudClient for CloudHandler {
+    async fn fetch_data(&self, spreadsheet_url: &str, sheet_name: &str) -> Result<TableData, FetchError> {
+        Ok(CloudHandler::fetch_data(self, spreadsheet_url, sheet_name).await?)
+    }
+
+    async fn list_sheets(&self, spreadsheet_url: &str) -> Result<Vec<String>, FetchError> {
+        Ok(CloudHandler::list_sheets(self, spreadsheet_url).await?)
+    }
+
+    async fn fetch_uncached(&self, spreadsheet_url: &str, sheet_name: &str) -> Result<TableData, FetchError> {
+        Ok(CloudHandler::fetch_uncached(self, spreadsheet_url, sheet_name).await?)
+    }
+}
+
+// Source name shown for the demo data
+pub const DEMO_URL: &str = "demo";
+
+// Canned sheets in the layout organizers export, title block included
+const DEMO_SHEETS: &[(&str, &[&[&str]])] = &[
+    ("Rifle", &[
+        &["Club Championship 2025"],
+        &["10m Air Rifle — Final"],
+        &["category", "first_name", "last_name", "organization", "part-1", "part-2", "result", "posit."],
+        &["Men", "Eero", "Laine", "Helsinki SC", "312.4", "311.9", "624.3", "1"],
+        &["Men", "Mikko", "Salo", "Tampere SK", "310.8", "311.2", "622.0", "2"],
+        &["Men", "Jari", "Koski", "Oulu SA", "309.5", "310.1", "619.6", "3"],
+        &["Men", "Antti", "Virta", "Turku SS", "308.2", "309.0", "617.2", "4"],
+        &["Women", "Anna", "Heikkinen", "Helsinki SC", "313.0", "312.2", "625.2", "1"],
+        &["Women", "Laura", "Niemi", "Kuopio AS", "311.6", "310.4", "622.0", "2"],
+        &["Women", "Sanna", "Mäkelä", "Tampere SK", "309.9", "310.7", "620.6", "3"],
+    ]),
+    ("Pistol", &[
+        &["Club Championship 2025"],
+        &["10m Air Pistol — Qualification"],
+        &["category", "first_name", "last_name", "organization", "part-1", "part-2", "result", "posit."],
+        &["Men", "Pekka", "Lehto", "Oulu SA", "288", "290", "578", "1"],
+        &["Men", "Timo", "Rantanen", "Turku SS", "286", "289", "575", "2"],
+        &["Men", "Ville", "Hakala", "Helsinki SC", "285", "287", "572", "3"],
+        &["Women", "Kaisa", "Aalto", "Kuopio AS", "287", "286", "573", "1"],
+        &["Women", "Emma", "Lindholm", "Helsinki SC", "284", "285", "569", "2"],
+    ]),
+];
+
+// Serves the demo sheets through the same processing as real fetches,
+// so the app can be shown without credentials or a network
+pub struct MockCloudHandler {
+    columns: ColumnConfig,
+}
+
+impl MockCloudHandler {
+    pub fn new(columns: ColumnConfig) -> Self {
+        MockCloudHandler { columns }
+    }
+
+    pub fn sheet_names() -> Vec<String> {
+        DEMO_SHEETS.iter().map(|(name, _)| name.to_string()).collect()
+    }
+}
+
+#[async_trait]
+impl CloudClient for MockCloudHandler {
+    async fn fetch_data(&self, _spreadsheet_url: &str, sheet_name: &str) -> Result<TableData, FetchError> {
+        // An unnamed sheet means the first one, as with the real API
+        let rows = DEMO_SHEETS.iter()
+            .find(|(name, _)| sheet_name.is_empty() || name.eq_ignore_ascii_case(sheet_name))
+            .map(|(_, rows)| *rows)
+            .ok_or_else(|| FetchError::Failed(format!("no demo sheet named '{}'", sheet_name)))?;
+        
+        let values = rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        
+        Ok(process_values(values, &self.columns))
+    }
+
+    async fn list_sheets(&self, _spreadsheet_url: &str) -> Result<Vec<String>, FetchError> {
+        Ok(Self::sheet_names())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn missing_credentials_file_is_named() {
+        let handler = CloudHandler::new(ColumnConfig::default())
+            .with_credentials(PathBuf::from("no/such/credentials.json"));
+        
+        let err = handler.authenticate().await.err().unwrap();
+        assert_eq!(err.to_string(), "credentials file not found at no/such/credentials.json");
+    }
+
+    const ID: &str = "1AbCdEfGhIjKlMnOpQrStUvWxYz0123456789_-";
+
+    fn id_of(url: &str) -> Option<&str> {
+        CloudHandler::extract_spreadsheet_id(url).ok()
+    }
+
+    #[test]
+    fn accepts_edit_urls() {
+        let url = format!("https://docs.google.com/spreadsheets/d/{}/edit#gid=0", ID);
+        assert_eq!(id_of(&url), Some(ID));
+    }
+
+    #[test]
+    fn accepts_urls_without_a_trailing_path() {
+        let url = format!("https://docs.google.com/spreadsheets/d/{}", ID);
+        assert_eq!(id_of(&url), Some(ID));
+    }
+
+    #[test]
+    fn accepts_published_urls() {
+        let url = "https://docs.google.com/spreadsheets/d/e/2PACX-1vRabcdefghijklmnop/pubhtml";
+        assert_eq!(id_of(url), Some("2PACX-1vRabcdefghijklmnop"));
+        assert!(CloudHandler::is_published_id("2PACX-1vRabcdefghijklmnop"));
+    }
+
+    #[test]
+    fn accepts_bare_ids() {
+        assert_eq!(id_of(ID), Some(ID));
+        assert_eq!(id_of(&format!("  {}  ", ID)), Some(ID));
+    }
+
+    #[test]
+    fn rejects_urls_without_an_id() {
+        let err = CloudHandler::extract_spreadsheet_id("https://example.com/results").err().unwrap();
+        assert_eq!(err.to_string(), "Couldn't find a spreadsheet ID in that URL");
+        assert_eq!(id_of("results"), None);
+        assert_eq!(id_of("https://docs.google.com/spreadsheets/d/"), None);
+    }
+
+    #[test]
+    fn public_export_url_names_the_sheet() {
+        let url = CloudHandler::public_export_url(ID, "Results 1").unwrap();
+        assert_eq!(
+            url.as_str(),
+            format!("https://docs.google.com/spreadsheets/d/{}/gviz/tq?tqx=out%3Acsv&sheet=Results+1", ID)
+        );
+        
+        // The first sheet when none is named
+        let url = CloudHandler::public_export_url(ID, "").unwrap();
+        assert_eq!(url.as_str(), format!("https://docs.google.com/spreadsheets/d/{}/gviz/tq?tqx=out%3Acsv", ID));
+    }
+
+    #[test]
+    fn published_sheets_use_the_pub_export() {
+        let url = CloudHandler::public_export_url("2PACX-1vRabcdefghijklmnop", "").unwrap();
+        assert_eq!(url.as_str(), "https://docs.google.com/spreadsheets/d/e/2PACX-1vRabcdefghijklmnop/pub?output=csv");
+    }
+
+    #[test]
+    fn sheet_range_is_quoted() {
+        assert_eq!(sheet_range(""), "'Sheet1'");
+        assert_eq!(sheet_range("Final 2"), "'Final 2'");
+        assert_eq!(sheet_range("Men's"), "'Men''s'");
+    }
+
+    #[test]
+    fn only_transient_errors_are_retried() {
+        let failure = |status: u16| google_sheets4::Error::Failure(
+            google_sheets4::hyper::Response::builder()
+                .status(status)
+                .body(google_sheets4::hyper::Body::empty())
+                .unwrap()
+        );
+        
+        assert!(is_transient(&failure(503)));
+        assert!(is_transient(&google_sheets4::Error::Io(std::io::Error::from(ErrorKind::ConnectionReset))));
+        assert!(!is_transient(&failure(403)));
+        assert!(!is_transient(&failure(404)));
+        assert!(!is_transient(&google_sheets4::Error::Cancelled));
+    }
+
+    #[test]
+    fn keeps_columns_past_z() {
+        let mut headers = vec!["category".to_string(), "name".to_string()];
+        headers.extend((1..=28).map(|stage| format!("part-{}", stage)));
+        headers.push("info".to_string());
+        let mut row = vec!["M".to_string(), "Eero".to_string()];
+        row.extend((1..=28).map(|stage| stage.to_string()));
+        row.push("note".to_string());
+        
+        let data = process_values(vec![headers, row], &ColumnConfig::default());
+        
+        // 31 columns, the last one hidden by the default hide list
+        assert_eq!(data.headers.len(), 31);
+        assert_eq!(data.rows[0][29], "28");
+        let shown = data.visible_view();
+        assert_eq!(shown.headers.len(), 30);
+        assert_eq!(shown.headers[29], "S28");
+    }
+
+    fn values(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter().map(|row| row.iter().map(|cell| cell.to_string()).collect()).collect()
+    }
+
+    #[test]
+    fn header_starts_at_the_category_row() {
+        let data = process_values(values(&[
+            &["SM 2026 Lahti"],
+            &["10 m ilmakivääri", "12.6.2026"],
+            &[],
+            &["category", "first_name", "result"],
+            &["M", "Eero", "624.3"],
+        ]), &ColumnConfig::default());
+        
+        assert_eq!(data.headers, vec!["Series", "Name", "Result"]);
+        assert_eq!(data.rows, vec![vec!["M", "Eero", "624.3"]]);
+        assert_eq!(data.title.as_deref(), Some("SM 2026 Lahti"));
+        assert_eq!(data.subtitle.as_deref(), Some("10 m ilmakivääri — 12.6.2026"));
+    }
+
+    #[test]
+    fn first_row_is_the_header_without_a_marker() {
+        let data = process_values(values(&[&["first_name", "result"], &["Eero", "624.3"]]), &ColumnConfig::default());
+        
+        assert_eq!(data.headers, vec!["Name", "Result"]);
+        assert_eq!(data.rows.len(), 1);
+        assert_eq!(data.title, None);
+    }
+
+    #[test]
+    fn hidden_columns_are_kept_but_not_shown() {
+        let data = process_values(values(&[
+            &["category", "sport_id", "first_name", "info"],
+            &["M", "7", "Eero", "x"],
+        ]), &ColumnConfig::default());
+        
+        assert_eq!(data.visible, vec![true, false, true, false]);
+        assert_eq!(data.rows[0][1], "7");
+        
+        let shown = data.visible_view();
+        assert_eq!(shown.headers, vec!["Series", "Name"]);
+        assert_eq!(shown.rows[0], vec!["M", "Eero"]);
+    }
+
+    #[test]
+    fn skips_empty_rows() {
+        let data = process_values(values(&[
+            &["category", "first_name", "result"],
+            &["M", "Eero", "624.3"],
+            &["", " ", ""],
+            &[],
+            &["N", "Aino", "618.0"],
+        ]), &ColumnConfig::default());
+        
+        assert_eq!(data.rows.len(), 2);
+        assert_eq!(data.rows[1][1], "Aino");
+    }
+
+    #[test]
+    fn mappings_above_the_header_rename_columns() {
+        let data = process_values(values(&[
+            &["Finals", "result=Points"],
+            &["category", "first_name", "result"],
+            &["M", "Eero", "624.3"],
+        ]), &ColumnConfig::default());
+        
+        assert_eq!(data.headers, vec!["Series", "Name", "Points"]);
+        assert_eq!(data.title.as_deref(), Some("Finals"));
+    }
+
+    #[test]
+    fn aligns_rows_to_the_header() {
+        let data = process_values(values(&[
+            &["category", "first_name", "result"],
+            &["M", "Eero", "624.3", "stray", "9"],
+            &["M", "Matti"],
+        ]), &ColumnConfig { min_row_fill: 0.0, ..ColumnConfig::default() });
+        
+        // Extra trailing cells are cut, a short row is padded
+        assert_eq!(data.rows[0], vec!["M", "Eero", "624.3"]);
+        assert_eq!(data.rows[1], vec!["M", "Matti", ""]);
+        assert_eq!(data.repaired_rows, 1);
     }
 }
@@ -0,0 +1,540 @@
+// src/columns.rs
+use iced::Color;
+use regex::Regex;
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::data_types::{parse_number, TableData};
+use crate::header::{self, DEFAULT_REPLACEMENTS};
+use crate::ui::parse_hex;
+use crate::value::{format_number, normalize_whitespace};
+
+const CONFIG_FILE: &str = "columns.toml";
+
+// Internal columns hidden by default
+const DEFAULT_HIDDEN: &[&str] = &[
+    "sport_id", "team_members", "team_name",
+    "info", "result_code", "position_pre",
+];
+
+// First cell of the header row in sheets with a title block above the table
+const DEFAULT_HEADER_MARKERS: &[&str] = &["category"];
+
+// First cells of a trailing totals row, when strip_totals is on
+const DEFAULT_TOTAL_MARKERS: &[&str] = &["total", "yhteensä"];
+
+// Rows with fewer than half the header's cells are dropped as truncated
+const DEFAULT_MIN_ROW_FILL: f32 = 0.5;
+
+// Rule column that colors every cell of the row
+const ALL_COLUMNS: &str = "*";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ColumnConfig {
+    pub hide: Vec<String>,                   // Headers to hide, compared as set by hide_match
+    pub hide_match: HideMatch,
+    pub replacements: Vec<(String, String)>, // (header substring, display name)
+    pub order: Vec<String>,                  // Display names shown first, in this order
+    pub keep_spacing: Vec<String>,           // Display names whose cells aren't whitespace-normalized
+    pub wrap: Vec<String>,                   // Display names whose long cells wrap onto more lines
+    pub clip: Vec<String>,                   // Display names whose long cells are cut off without an ellipsis
+    pub header_markers: Vec<String>,         // First-cell values marking the header row in sheets
+    pub min_row_fill: f32,                   // Shortest kept row, as a fraction of the header length
+    pub strip_totals: bool,                  // Move trailing totals rows out of the results
+    pub total_markers: Vec<String>,          // First-cell values marking a totals row
+    pub rules: Vec<ColorRule>,               // Conditional cell colors, first match wins
+    pub formats: Vec<NumberFormat>,          // Display rounding for numeric columns
+    pub key_columns: Vec<String>,            // Display names that together identify a row, e.g. ["Team", "Leg"]
+}
+
+// How a numeric column is shown, without changing the stored value, e.g.
+//
+// [[formats]]
+// column = "Result"
+// decimals = 1
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct NumberFormat {
+    pub column: String,              // Display name of the column
+    pub decimals: usize,
+    pub thousands: Option<String>,   // Separator between groups of thousands, e.g. " "
+}
+
+// How a header is compared with the hide list, after trimming and lowercasing both
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HideMatch {
+    Exact,      // "info" hides only "info"
+    Prefix,     // "info" also hides "info_2"
+    Substring,  // "info" also hides "information_public", the old behaviour
+}
+
+impl HideMatch {
+    fn matches(self, header: &str, hidden: &str) -> bool {
+        match self {
+            HideMatch::Exact => header == hidden,
+            HideMatch::Prefix => header.starts_with(hidden),
+            HideMatch::Substring => header.contains(hidden),
+        }
+    }
+}
+
+// What happens to a cell too long for its column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    Wrap,      // Grows the row, so the frozen column and the body can drift apart
+    Ellipsis,  // Cut short with "…", the full value shown on hover
+    Clip,
+}
+
+// Colors a cell when every condition given holds, e.g.
+//
+// [[rules]]
+// column = "Result"
+// below = 550
+// bg = "#991a1a"
+//
+// The condition can read another column, hidden ones included, and "*" colors
+// the whole row:
+//
+// [[rules]]
+// column = "*"
+// source = "club"
+// matches = "^Helsinki"
+// fg = "#02539c"
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ColorRule {
+    pub column: String,           // Display name of the column colored, or "*" for every column
+    pub source: Option<String>,   // Column the conditions test, the colored one when unset
+    pub above: Option<f64>,       // Value strictly greater than this
+    pub below: Option<f64>,       // Value strictly less than this
+    pub equals: Option<f64>,
+    pub matches: Option<String>,  // Regular expression the cell text must match
+    pub bg: Option<String>,
+    pub fg: Option<String>,
+    #[serde(skip)]
+    regex: Option<Regex>,
+}
+
+impl ColorRule {
+    fn applies(&self, cell: &str, value: Option<f64>) -> bool {
+        let compare = |bound: Option<f64>, holds: fn(f64, f64) -> bool| match bound {
+            Some(bound) => value.map_or(false, |value| holds(value, bound)),
+            None => true,
+        };
+        
+        // A pattern that failed to compile never matches
+        let text_matches = match self.matches {
+            Some(_) => self.regex.as_ref().map_or(false, |regex| regex.is_match(cell.trim())),
+            None => true,
+        };
+        
+        compare(self.above, |value, bound| value > bound)
+            && compare(self.below, |value, bound| value < bound)
+            && compare(self.equals, |value, bound| value == bound)
+            && text_matches
+    }
+
+    // (background, foreground), either left to the default styling when unset or invalid
+    pub fn colors(&self) -> (Option<Color>, Option<Color>) {
+        (
+            self.bg.as_deref().and_then(parse_hex),
+            self.fg.as_deref().and_then(parse_hex),
+        )
+    }
+}
+
+impl Default for ColumnConfig {
+    fn default() -> Self {
+        ColumnConfig {
+            hide: DEFAULT_HIDDEN
+                .iter()
+                .map(|col| col.to_string())
+                .collect(),
+            hide_match: HideMatch::Exact,
+            replacements: DEFAULT_REPLACEMENTS
+                .iter()
+                .map(|(original, replacement)| (original.to_string(), replacement.to_string()))
+                .collect(),
+            order: Vec::new(),
+            keep_spacing: Vec::new(),
+            wrap: Vec::new(),
+            clip: Vec::new(),
+            header_markers: DEFAULT_HEADER_MARKERS
+                .iter()
+                .map(|marker| marker.to_string())
+                .collect(),
+            min_row_fill: DEFAULT_MIN_ROW_FILL,
+            strip_totals: false,
+            total_markers: DEFAULT_TOTAL_MARKERS
+                .iter()
+                .map(|marker| marker.to_string())
+                .collect(),
+            rules: Vec::new(),
+            formats: Vec::new(),
+            key_columns: Vec::new(),
+        }
+    }
+}
+
+impl ColumnConfig {
+    // Load columns.toml from next to the executable, falling back to the built-in defaults
+    pub fn load() -> Self {
+        let mut config = match Self::config_path() {
+            Some(path) => Self::read(&path),
+            None => ColumnConfig::default(),
+        };
+        
+        for rule in config.rules.iter_mut() {
+            rule.regex = rule.matches.as_deref().and_then(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(err) => {
+                    log::warn!("Ignoring the pattern \"{}\" of the rule for {}: {}", pattern, rule.column, err);
+                    None
+                }
+            });
+        }
+        
+        config
+    }
+
+    // A missing file just means the defaults; an unreadable or malformed one is reported
+    fn read(path: &Path) -> Self {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return ColumnConfig::default(),
+            Err(err) => {
+                log::warn!("Could not read {}, using the default columns: {}", path.display(), err);
+                return ColumnConfig::default();
+            }
+        };
+        
+        match toml::from_str(&contents) {
+            Ok(config) => config,
+            Err(err) => {
+                log::warn!("Ignoring {}, using the default columns: {}", path.display(), err);
+                ColumnConfig::default()
+            }
+        }
+    }
+
+    fn config_path() -> Option<PathBuf> {
+        let exe = env::current_exe().ok()?;
+        Some(exe.parent()?.join(CONFIG_FILE))
+    }
+
+    pub fn should_hide(&self, header: &str) -> bool {
+        let header = header.trim().to_lowercase();
+        self.hide.iter()
+            .any(|col| self.hide_match.matches(&header, &col.trim().to_lowercase()))
+    }
+
+    // Add header mappings written in the sheet's preamble as "internal=Display" cells.
+    // They are checked before the configured replacements.
+    pub fn with_sheet_replacements<'a, I: IntoIterator<Item = &'a str>>(&self, cells: I) -> ColumnConfig {
+        let mut sheet_replacements: Vec<(String, String)> = cells.into_iter()
+            .filter_map(parse_mapping)
+            .collect();
+        
+        let mut config = self.clone();
+        if !sheet_replacements.is_empty() {
+            sheet_replacements.extend(config.replacements);
+            config.replacements = sheet_replacements;
+        }
+        config
+    }
+
+    // The first coloring rule that applies to a cell. `lookup` reads a cell of the
+    // same row by column name, as text and numeric value.
+    pub fn rule_for<'a>(&self, header: &str, lookup: impl Fn(&str) -> Option<(&'a str, Option<f64>)>) -> Option<&ColorRule> {
+        self.rules.iter()
+            .filter(|rule| rule.column.trim() == ALL_COLUMNS || header.trim().eq_ignore_ascii_case(rule.column.trim()))
+            .find(|rule| {
+                let source = rule.source.as_deref().unwrap_or(&rule.column);
+                lookup(source.trim()).map_or(false, |(cell, value)| rule.applies(cell, value))
+            })
+    }
+
+    // Every row is one line high unless configured otherwise, which keeps the
+    // virtualized table aligned
+    pub fn overflow_for(&self, header: &str) -> Overflow {
+        let listed = |names: &[String]| names.iter().any(|name| header.trim().eq_ignore_ascii_case(name.trim()));
+        if listed(&self.wrap) {
+            Overflow::Wrap
+        } else if listed(&self.clip) {
+            Overflow::Clip
+        } else {
+            Overflow::Ellipsis
+        }
+    }
+
+    // The cell as it should be displayed, rounded when its column has a number format
+    pub fn format_cell(&self, header: &str, value: Option<f64>) -> Option<String> {
+        let format = self.formats.iter()
+            .find(|format| header.trim().eq_ignore_ascii_case(format.column.trim()))?;
+        Some(format_number(value?, format.decimals, format.thousands.as_deref()))
+    }
+
+    pub fn is_header_marker(&self, cell: &str) -> bool {
+        let cell = header::strip_bom(cell).trim().to_lowercase();
+        self.header_markers.iter()
+            .any(|marker| marker.trim().to_lowercase() == cell)
+    }
+
+    // Pad rows that are a little short and drop the ones that are mostly missing,
+    // counting both so the viewer can report them
+    pub fn repair_rows(&self, data: &mut TableData) {
+        let width = data.headers.len();
+        let before = data.rows.len();
+        
+        // Only shown cells count, so a missing hidden one doesn't drop or flag the row
+        let visible: Vec<bool> = (0..width).map(|column| data.is_visible(column)).collect();
+        let shown_cells = |len: usize| visible.iter().take(len).filter(|&&shown| shown).count();
+        let shown_width = shown_cells(width);
+        let min_cells = (shown_width as f32 * self.min_row_fill).ceil() as usize;
+        
+        data.rows.retain(|row| shown_cells(row.len()) >= min_cells);
+        data.dropped_rows = before - data.rows.len();
+        data.repaired_rows = 0;
+        
+        for row in data.rows.iter_mut() {
+            if row.len() < width {
+                if shown_cells(row.len()) < shown_width {
+                    data.repaired_rows += 1;
+                }
+                row.resize(width, String::new());
+            }
+        }
+    }
+
+    // Move totals rows at the end of the table into data.totals. Off unless configured,
+    // so a competitor who happens to be called "Total" is never lost.
+    pub fn strip_totals(&self, data: &mut TableData) {
+        if !self.strip_totals {
+            return;
+        }
+        
+        while let Some(row) = data.rows.last() {
+            // Judged on the shown cells only
+            let row: Vec<String> = row.iter()
+                .enumerate()
+                .filter(|(column, _)| data.is_visible(*column))
+                .map(|(_, cell)| cell.clone())
+                .collect();
+            if !self.is_totals_row(&row) {
+                break;
+            }
+            if let Some(row) = data.rows.pop() {
+                data.totals.insert(0, row);
+            }
+        }
+    }
+
+    // A row labelled like "TOTAL", or empty apart from a single number
+    fn is_totals_row(&self, row: &[String]) -> bool {
+        let labelled = row.first().map_or(false, |cell| {
+            let cell = cell.trim().to_lowercase();
+            self.total_markers.iter().any(|marker| marker.trim().to_lowercase() == cell)
+        });
+        
+        let filled: Vec<&String> = row.iter().filter(|cell| !cell.trim().is_empty()).collect();
+        let lone_aggregate = row.len() > 2 && filled.len() == 1 && parse_number(filled[0]).is_some();
+        
+        labelled || lone_aggregate
+    }
+
+    // Tidy stray and non-breaking spaces, except in columns where spacing matters
+    pub fn normalize_cells(&self, data: &mut TableData) {
+        let normalize: Vec<bool> = data.headers.iter()
+            .map(|header| !self.keep_spacing.iter().any(|name| header.trim().eq_ignore_ascii_case(name.trim())))
+            .collect();
+        
+        for row in data.rows.iter_mut() {
+            for (cell, &normalize) in row.iter_mut().zip(normalize.iter()) {
+                if normalize {
+                    *cell = normalize_whitespace(cell);
+                }
+            }
+        }
+    }
+
+    // Move the listed columns to the front, keeping the rest in source order.
+    // Names that match no column are skipped.
+    pub fn apply_order(&self, data: &mut TableData) {
+        if self.order.is_empty() {
+            return;
+        }
+        
+        let mut permutation: Vec<usize> = Vec::with_capacity(data.headers.len());
+        for name in &self.order {
+            let found = data.column_index(name.trim());
+            if let Some(i) = found {
+                if !permutation.contains(&i) {
+                    permutation.push(i);
+                }
+            }
+        }
+        permutation.extend((0..data.headers.len()).filter(|i| !permutation.contains(i)));
+        
+        data.headers = permutation.iter().map(|&i| data.headers[i].clone()).collect();
+        if !data.visible.is_empty() {
+            data.visible = permutation.iter().map(|&i| data.is_visible(i)).collect();
+        }
+        for row in data.rows.iter_mut().chain(data.totals.iter_mut()) {
+            // Pad short rows so later cells don't shift into the wrong column
+            let reordered: Vec<String> = permutation.iter()
+                .map(|&i| row.get(i).cloned().unwrap_or_default())
+                .collect();
+            *row = reordered;
+        }
+    }
+}
+
+// Parse an "internal=Display" mapping cell
+pub fn parse_mapping(cell: &str) -> Option<(String, String)> {
+    let (original, replacement) = cell.split_once('=')?;
+    let (original, replacement) = (original.trim(), replacement.trim());
+    if original.is_empty() || replacement.is_empty() {
+        return None;
+    }
+    Some((original.to_string(), replacement.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csv_handler::CSVHandler;
+
+    fn parse(content: &str, columns: &ColumnConfig) -> TableData {
+        CSVHandler::parse_bytes(content.as_bytes(), columns).unwrap()
+    }
+
+    #[test]
+    fn custom_hide_list_removes_columns() {
+        let columns = ColumnConfig {
+            hide: vec!["club".to_string(), "bib".to_string()],
+            ..ColumnConfig::default()
+        };
+        let data = parse("bib,name,club,result\n7,Eero,Helsinki SC,624.3\n", &columns);
+        
+        assert_eq!(data.visible_view().headers, vec!["name", "Result"]);
+        assert_eq!(data.visible_view().rows[0], vec!["Eero", "624.3"]);
+    }
+
+    #[test]
+    fn default_hide_list_applies_without_a_file() {
+        let data = parse("sport_id,name,info\n1,Eero,x\n", &ColumnConfig::default());
+        assert_eq!(data.visible_view().headers, vec!["name"]);
+    }
+
+    #[test]
+    fn malformed_file_falls_back_to_defaults() {
+        let path = env::temp_dir().join(format!("svr-columns-{}.toml", std::process::id()));
+        fs::write(&path, "hide = [\"unterminated").unwrap();
+        let config = ColumnConfig::read(&path);
+        fs::remove_file(&path).ok();
+        
+        assert_eq!(config.hide, ColumnConfig::default().hide);
+    }
+
+    #[test]
+    fn normalizes_cells_unless_spacing_is_kept() {
+        let columns = ColumnConfig {
+            keep_spacing: vec!["Club".to_string()],
+            ..ColumnConfig::default()
+        };
+        let data = parse("name,organization\n\"  Team   A \u{00A0}\",\"  Team   A \"\n", &columns);
+        
+        assert_eq!(data.headers, vec!["name", "Club"]);
+        assert_eq!(data.rows[0], vec!["Team A", "  Team   A "]);
+    }
+
+    #[test]
+    fn drops_rows_far_short_of_the_header_and_pads_the_rest() {
+        let data = parse(
+            "name,bib,club,time,score,note\nEero,7,HSC,1:02.5,98,x\nAino,12\nMatti,3,OSA,1:04.1,97\n",
+            &ColumnConfig::default(),
+        );
+        
+        // 2 of 6 cells is under the default half, 5 of 6 is padded
+        assert_eq!(data.dropped_rows, 1);
+        assert_eq!(data.repaired_rows, 1);
+        assert_eq!(data.rows.len(), 2);
+        assert_eq!(data.rows[1], vec!["Matti", "3", "OSA", "1:04.1", "97", ""]);
+    }
+
+    #[test]
+    fn row_fill_threshold_is_configurable() {
+        let columns = ColumnConfig { min_row_fill: 0.0, ..ColumnConfig::default() };
+        let data = parse("name,bib,club,time,score,note\nEero,7,HSC,1:02.5,98,x\nAino,12\n", &columns);
+        
+        assert_eq!(data.dropped_rows, 0);
+        assert_eq!(data.repaired_rows, 1);
+        assert_eq!(data.rows[1], vec!["Aino", "12", "", "", "", ""]);
+    }
+
+    fn hides(hide_match: HideMatch, header: &str) -> bool {
+        let columns = ColumnConfig { hide: vec!["info".to_string()], hide_match, ..ColumnConfig::default() };
+        columns.should_hide(header)
+    }
+
+    #[test]
+    fn exact_matching_is_the_default() {
+        assert_eq!(ColumnConfig::default().hide_match, HideMatch::Exact);
+        assert!(hides(HideMatch::Exact, " INFO "));
+        assert!(!hides(HideMatch::Exact, "information"));
+        assert!(!hides(HideMatch::Exact, "info_2"));
+        
+        // The defaults no longer catch longer names that contain them
+        assert!(!ColumnConfig::default().should_hide("team_name_display"));
+    }
+
+    #[test]
+    fn prefix_matching_hides_longer_names() {
+        assert!(hides(HideMatch::Prefix, "info_2"));
+        assert!(hides(HideMatch::Prefix, "information"));
+        assert!(!hides(HideMatch::Prefix, "extra_info"));
+    }
+
+    #[test]
+    fn substring_matching_hides_any_containing_name() {
+        assert!(hides(HideMatch::Substring, "extra_info"));
+        assert!(hides(HideMatch::Substring, "information_public"));
+        assert!(!hides(HideMatch::Substring, "result"));
+    }
+
+    #[test]
+    fn hide_match_is_read_from_the_file() {
+        let columns: ColumnConfig = toml::from_str("hide_match = \"prefix\"").unwrap();
+        assert_eq!(columns.hide_match, HideMatch::Prefix);
+    }
+
+    #[test]
+    fn formats_only_configured_numeric_columns() {
+        let columns = ColumnConfig {
+            formats: vec![NumberFormat { column: "Result".to_string(), decimals: 1, thousands: None }],
+            ..ColumnConfig::default()
+        };
+        
+        assert_eq!(columns.format_cell("result", Some(612.3000000001)).as_deref(), Some("612.3"));
+        assert_eq!(columns.format_cell("Result", None), None);
+        assert_eq!(columns.format_cell("Rank", Some(1.0)), None);
+    }
+
+    #[test]
+    fn rules_can_test_a_hidden_column() {
+        let columns: ColumnConfig = toml::from_str(
+            "[[rules]]\ncolumn = \"Name\"\nsource = \"info\"\nequals = 1\nbg = \"#ff0000\"\n"
+        ).unwrap();
+        let data = parse("name,info,result\nEero,1,624.3\nAino,0,618.0\n", &columns);
+        
+        assert!(!data.is_visible(1));
+        assert!(columns.rule_for("Name", |name| data.named_cell(0, name)).is_some());
+        assert!(columns.rule_for("Name", |name| data.named_cell(1, name)).is_none());
+    }
+}
@@ -0,0 +1,237 @@
+// src/config.rs
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::data_types::{DataSource, SortDir};
+use crate::playlist::PlaylistEntry;
+
+const CONFIG_DIR: &str = "score_viewer";
+const CONFIG_FILE: &str = "config.json";
+const CREDENTIALS_ENV: &str = "SVR_CREDENTIALS";
+
+// Sanity bounds for a restored window, in logical pixels
+const MIN_WINDOW_SIZE: u32 = 200;
+const MAX_SCREEN_EXTENT: u32 = 16384;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub refresh_interval_secs: u64,
+    pub data_source: Option<DataSource>,
+    pub credentials_path: Option<PathBuf>,
+    pub request_timeout_secs: u64,
+    pub fullscreen: bool,
+    pub decorations: bool,
+    pub export_width: u32,
+    pub export_height: u32,
+    pub theme_path: Option<PathBuf>,
+    pub font_scale: f32,
+    pub auto_scroll_speed: f32,  // Pixels per second
+    pub page_size: usize,
+    pub max_rows: Option<usize>,  // Show only the top rows, for a crowded screen
+    pub sort: Option<(usize, SortDir)>,  // The view as it was left, restored on launch
+    pub filter: String,
+    pub facets: BTreeMap<String, BTreeSet<String>>,
+    pub show_clock: bool,
+    pub countdown_to: Option<String>,  // Local time such as "14:30" to count down to
+    pub window_size: (u32, u32),
+    pub window_position: Option<(i32, i32)>,  // Centered when unset
+    pub maximized: bool,
+    pub density: Density,
+    pub fit_width: bool,              // Stretch columns to fill the window instead of sizing to content
+    pub striped_rows: bool,           // Shade every other row
+    pub medals: bool,                 // Gold, silver and bronze behind the podium
+    pub change_highlights: bool,      // Flash rows that changed on a refresh
+    pub show_summary: bool,           // Per-category aggregates beside the table
+    pub favorites: Vec<String>,       // Row keys of competitors pinned above the table
+    pub playlist: Vec<PlaylistEntry>,  // Sources cycled through for signage, replacing data_source
+    pub title: Option<String>,     // Event banner, overrides the sheet's own title rows
+    pub subtitle: Option<String>,
+    pub footer_text: Option<String>,  // Replaces the built-in copyright caption
+    pub logo_path: Option<PathBuf>,   // Small image shown before the caption
+    pub font_path: Option<PathBuf>,   // TTF/OTF replacing the bundled font
+    pub font_family: Option<String>,  // Family name inside font_path, needed to select it
+    pub auto_theme: bool,             // Switch light/dark by time of day
+    pub light_from_hour: u32,         // Local hour the light theme starts
+    pub dark_from_hour: u32,          // Local hour the dark theme starts
+    pub idle_after_mins: Option<u64>,  // Protect the screen once nothing has changed for this long, off when unset
+    pub idle_mode: IdleMode,
+    #[serde(skip)]
+    pub demo: bool,                   // Canned sample data, set by --demo for this launch only
+}
+
+// How tightly the table is packed, independent of the font scale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Density {
+    Comfortable,  // Projector-friendly
+    Compact,      // More rows on a laptop preview
+}
+
+impl Density {
+    pub fn toggled(self) -> Self {
+        match self {
+            Density::Comfortable => Density::Compact,
+            Density::Compact => Density::Comfortable,
+        }
+    }
+
+    pub fn cell_padding(self) -> u16 {
+        match self {
+            Density::Comfortable => 5,
+            Density::Compact => 2,
+        }
+    }
+
+    // Unscaled table text size
+    pub fn text_size(self) -> f32 {
+        match self {
+            Density::Comfortable => 18.0,
+            Density::Compact => 15.0,
+        }
+    }
+}
+
+// What an idle display does against burn-in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdleMode {
+    Dim,    // Darken the whole window
+    Shift,  // Drift the content a few pixels every minute
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            refresh_interval_secs: 5,
+            data_source: None,
+            credentials_path: None,
+            request_timeout_secs: 10,
+            fullscreen: false,
+            decorations: false,
+            export_width: 1080,
+            export_height: 1350,
+            theme_path: None,
+            font_scale: 1.0,
+            auto_scroll_speed: 30.0,
+            page_size: 50,
+            max_rows: None,
+            sort: None,
+            filter: String::new(),
+            facets: BTreeMap::new(),
+            show_clock: false,
+            countdown_to: None,
+            window_size: (1024, 768),
+            window_position: None,
+            maximized: false,
+            density: Density::Comfortable,
+            fit_width: false,
+            striped_rows: false,
+            medals: true,
+            change_highlights: true,
+            show_summary: false,
+            favorites: Vec::new(),
+            playlist: Vec::new(),
+            title: None,
+            subtitle: None,
+            footer_text: None,
+            logo_path: None,
+            font_path: None,
+            font_family: None,
+            auto_theme: false,
+            light_from_hour: 7,
+            dark_from_hour: 20,
+            idle_after_mins: None,
+            idle_mode: IdleMode::Dim,
+            demo: false,
+        }
+    }
+}
+
+impl AppConfig {
+    // Load the persisted config from the OS config dir, falling back to defaults
+    pub fn load() -> Self {
+        Self::config_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    // Best effort, a failed save should never take down the display
+    pub fn save(&self) {
+        if let Some(path) = Self::config_path() {
+            if let Some(dir) = path.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            if let Ok(json) = serde_json::to_string_pretty(self) {
+                let _ = fs::write(path, json);
+            }
+        }
+    }
+
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.refresh_interval_secs)
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs)
+    }
+
+    // How long the display may sit unchanged and untouched before it idles
+    pub fn idle_after(&self) -> Option<Duration> {
+        self.idle_after_mins
+            .filter(|mins| *mins > 0)
+            .map(|mins| Duration::from_secs(mins * 60))
+    }
+
+    // The SVR_CREDENTIALS environment variable wins over the persisted path
+    pub fn credentials_path(&self) -> Option<PathBuf> {
+        env::var_os(CREDENTIALS_ENV)
+            .map(PathBuf::from)
+            .or_else(|| self.credentials_path.clone())
+    }
+
+    // Whether the dark theme is due at the given hour, or None when auto theme is off
+    pub fn scheduled_dark_mode(&self, hour: u32) -> Option<bool> {
+        if !self.auto_theme {
+            return None;
+        }
+        let (light, dark) = (self.light_from_hour, self.dark_from_hour);
+        let is_light = if light <= dark {
+            hour >= light && hour < dark
+        } else {
+            // Light period wraps past midnight
+            hour >= light || hour < dark
+        };
+        Some(!is_light)
+    }
+
+    // Not checked against the attached monitors: iced can't list them before the
+    // window exists. This only rules out sizes and positions no display setup would
+    // produce, so a position on a since-unplugged monitor still gets through.
+    pub fn window_size(&self) -> (u32, u32) {
+        let (width, height) = self.window_size;
+        if (MIN_WINDOW_SIZE..=MAX_SCREEN_EXTENT).contains(&width)
+            && (MIN_WINDOW_SIZE..=MAX_SCREEN_EXTENT).contains(&height) {
+            (width, height)
+        } else {
+            AppConfig::default().window_size
+        }
+    }
+
+    // A saved position is dropped if it would leave the title bar out of reach
+    pub fn window_position(&self) -> Option<(i32, i32)> {
+        let (x, y) = self.window_position?;
+        let (width, _) = self.window_size();
+        let extent = MAX_SCREEN_EXTENT as i32;
+        let on_screen = x > -(width as i32) + MIN_WINDOW_SIZE as i32 && x < extent && y >= 0 && y < extent;
+        if on_screen { Some((x, y)) } else { None }
+    }
+
+    fn config_path() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join(CONFIG_DIR).join(CONFIG_FILE))
+    }
+}
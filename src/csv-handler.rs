@@ -1,155 +1,458 @@
 // src/csv_handler.rs
-use std::path::Path;
-use csv::{ReaderBuilder, StringRecord};
-use std::io::{Read, BufReader};
-use std::fs::File;
+use std::borrow::Cow;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+use std::fs;
+use std::fmt;
+use std::error::Error;
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+use flate2::read::GzDecoder;
 use tokio::task;
 
+use crate::columns::ColumnConfig;
 use crate::data_types::TableData;
+use crate::header;
 
-pub struct CSVHandler {}
+#[derive(Debug)]
+pub enum CsvError {
+    Io(std::io::Error),
+    Parse(csv::Error),
+    Gzip(std::io::Error),
+    EmptyFile,
+    HeaderMismatch(PathBuf),  // A merged file whose columns differ from the first
+}
+
+// Every gzip stream starts with these two bytes
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+// Candidate delimiters, in order of preference when counts are tied
+const DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+
+// How many non-empty lines get a say in the delimiter
+const DELIMITER_SAMPLE_LINES: usize = 10;
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::Io(err) => write!(f, "Could not read file: {}", err),
+            CsvError::Parse(err) => write!(f, "Could not parse CSV: {}", err),
+            CsvError::Gzip(err) => write!(f, "Could not decompress file: {}", err),
+            CsvError::EmptyFile => write!(f, "The file is empty"),
+            CsvError::HeaderMismatch(path) => write!(
+                f, "{} has different columns from the first file", path.display()
+            ),
+        }
+    }
+}
+
+impl Error for CsvError {}
+
+impl From<std::io::Error> for CsvError {
+    fn from(err: std::io::Error) -> Self {
+        CsvError::Io(err)
+    }
+}
+
+impl From<csv::Error> for CsvError {
+    fn from(err: csv::Error) -> Self {
+        CsvError::Parse(err)
+    }
+}
+
+pub struct CSVHandler {
+    columns: ColumnConfig,
+}
 
 impl CSVHandler {
-    pub fn new() -> Self {
-        CSVHandler {}
+    pub fn new(columns: ColumnConfig) -> Self {
+        CSVHandler { columns }
     }
 
-    pub async fn read_csv<P: AsRef<Path> + Send + 'static>(&self, path: P) -> TableData {
+    pub async fn read_csv<P: AsRef<Path> + Send + 'static>(&self, path: P) -> Result<TableData, CsvError> {
+        let columns = self.columns.clone();
+        
         task::spawn_blocking(move || {
-            let mut data = TableData::empty();
+            // Read the raw bytes so the encoding can be detected before parsing
+            log::info!("Reading {}", path.as_ref().display());
+            let bytes = fs::read(&path)?;
+            Self::parse_bytes(&bytes, &columns)
+        }).await.unwrap_or_else(|err| Err(CsvError::Io(std::io::Error::new(std::io::ErrorKind::Other, err))))
+    }
 
-            // First check if file uses comma or semicolon as delimiter
-            let delimiter = Self::detect_delimiter(&path);
-            
-            let file = match File::open(&path) {
-                Ok(file) => file,
-                Err(_) => return data,
-            };
-            
-            let mut reader = ReaderBuilder::new()
-                .delimiter(delimiter as u8)
-                .flexible(true)
-                .from_reader(file);
-
-            // Process the CSV
-            let headers: Vec<String> = match reader.headers() {
-                Ok(headers) => headers.iter().map(String::from).collect(),
-                Err(_) => return data,  // Return empty data if headers can't be read
-            };
-
-            // Find columns to hide and process headers
-            let columns_to_hide = Self::get_columns_to_hide(&headers);
-            let (processed_headers, visible_columns) = Self::process_headers(headers, &columns_to_hide);
-            
-            data.headers = processed_headers;
-            
-            // Read and process rows
-            for result in reader.records() {
-                match result {
-                    Ok(record) => {
-                        // Skip empty rows
-                        if record.iter().all(|field| field.trim().is_empty()) {
-                            continue;
-                        }
-                        
-                        // Filter visible columns
-                        let filtered_row: Vec<String> = record.iter()
-                            .enumerate()
-                            .filter(|(i, _)| i < &visible_columns.len() && visible_columns[*i])
-                            .map(|(_, field)| field.to_string())
-                            .collect();
-                        
-                        data.rows.push(filtered_row);
-                    },
-                    Err(_) => continue,
+    // Read several files with the same columns, e.g. preliminary and final
+    // results, and show their rows one after another as a single table
+    pub async fn read_csv_files(&self, paths: Vec<PathBuf>) -> Result<TableData, CsvError> {
+        let mut merged: Option<TableData> = None;
+        
+        for path in paths {
+            let data = self.read_csv(path.clone()).await?;
+            match merged {
+                None => merged = Some(data),
+                Some(ref mut merged) => {
+                    let same = |a: &[String], b: &[String]| a.len() == b.len()
+                        && a.iter().zip(b).all(|(a, b)| a.trim().eq_ignore_ascii_case(b.trim()));
+                    if !same(&merged.headers, &data.headers) || merged.visible != data.visible {
+                        return Err(CsvError::HeaderMismatch(path));
+                    }
+                    
+                    merged.rows.extend(data.rows);
+                    merged.totals.extend(data.totals);
+                    merged.repaired_rows += data.repaired_rows;
+                    merged.dropped_rows += data.dropped_rows;
                 }
             }
-
-            data
-        }).await.unwrap_or_else(|_| TableData::empty())
+        }
+        
+        // Types are worked out again over the combined rows
+        let mut merged = merged.ok_or(CsvError::EmptyFile)?;
+        merged.infer_column_types();
+        Ok(merged)
     }
 
-    fn detect_delimiter<P: AsRef<Path>>(path: P) -> char {
-        let file = match File::open(path) {
-            Ok(file) => file,
-            Err(_) => return ',', // Default to comma if file can't be opened
+    // Parse raw CSV bytes, shared with the public Google Sheets export
+    pub fn parse_bytes(bytes: &[u8], columns: &ColumnConfig) -> Result<TableData, CsvError> {
+        let mut data = TableData::empty();
+
+        if bytes.is_empty() {
+            return Err(CsvError::EmptyFile);
+        }
+        
+        let bytes = Self::decompress(bytes)?;
+        let (content, encoding) = Self::decode(&bytes);
+        data.encoding = Some(encoding.name());
+        
+        // Check which delimiter the file uses
+        let delimiter = Self::detect_delimiter(&content);
+        log::debug!("Detected {} encoding and {:?} delimiter", encoding.name(), delimiter);
+        
+        let mut reader = ReaderBuilder::new()
+            .delimiter(delimiter as u8)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(content.as_bytes());
+
+        // Read every record first so a title block above the header can be skipped.
+        // A malformed record fails the whole read rather than quietly losing a row.
+        let records = reader.records().collect::<Result<Vec<StringRecord>, csv::Error>>()?;
+        
+        // The header is the first row starting with a marker such as "category",
+        // or the first row when there is none
+        let start_index = records.iter()
+            .position(|record| record.get(0).map_or(false, |cell| columns.is_header_marker(cell)))
+            .unwrap_or(0);
+
+        // Process the CSV
+        let headers: Vec<String> = match records.get(start_index) {
+            Some(record) => record.iter().map(String::from).collect(),
+            None => return Err(CsvError::EmptyFile),
         };
+        if headers.iter().all(|header| header.trim().is_empty()) {
+            return Err(CsvError::EmptyFile);
+        }
+
+        // Rows above the header carry the event name and date
+        data.set_preamble(records[..start_index].iter().map(|record| record.iter()));
+
+        // Mappings in the preamble rename columns for this file only
+        let columns = &columns.with_sheet_replacements(
+            records[..start_index].iter().flat_map(|record| record.iter())
+        );
+
+        // Find columns to hide and process headers
+        let (processed_headers, visible_columns) = Self::process_headers(headers, columns);
+        
+        data.headers = processed_headers;
+        data.visible = visible_columns;
+        
+        // Read and process rows
+        for record in records.iter().skip(start_index + 1) {
+            // Skip empty rows
+            if record.iter().all(|field| field.trim().is_empty()) {
+                continue;
+            }
+            
+            // Hidden cells are kept for coloring rules and export; cells past the header are not
+            let row: Vec<String> = record.iter()
+                .take(data.headers.len())
+                .map(String::from)
+                .collect();
+            data.rows.push(row);
+        }
+        
+        columns.repair_rows(&mut data);
+        columns.normalize_cells(&mut data);
+        columns.strip_totals(&mut data);
+        columns.apply_order(&mut data);
+        data.key_columns = columns.key_columns.clone();
+        data.infer_column_types();
+        
+        log::info!(
+            "Parsed {} rows and {} columns ({} repaired, {} dropped)",
+            data.rows.len(), data.headers.len(), data.repaired_rows, data.dropped_rows
+        );
+
+        Ok(data)
+    }
+
+    // Write processed data back out as a plain comma-separated file
+    pub fn write_csv(path: &Path, data: &TableData) -> Result<(), CsvError> {
+        let mut writer = WriterBuilder::new()
+            .flexible(true)
+            .from_path(path)?;
+        
+        writer.write_record(&data.headers)?;
+        for row in data.rows.iter() {
+            writer.write_record(row)?;
+        }
+        writer.flush()?;
+        
+        Ok(())
+    }
+
+    // Compressed .csv.gz files and feeds are recognised by their magic bytes,
+    // whatever the file name or Content-Encoding says
+    fn decompress(bytes: &[u8]) -> Result<Cow<[u8]>, CsvError> {
+        if !bytes.starts_with(&GZIP_MAGIC) {
+            return Ok(Cow::Borrowed(bytes));
+        }
+        
+        let mut decompressed = Vec::new();
+        GzDecoder::new(bytes).read_to_end(&mut decompressed).map_err(CsvError::Gzip)?;
+        log::debug!("Decompressed {} gzip bytes to {}", bytes.len(), decompressed.len());
+        Ok(Cow::Owned(decompressed))
+    }
+
+    fn decode(bytes: &[u8]) -> (String, &'static Encoding) {
+        // A byte order mark decides the encoding outright
+        if let Some((encoding, bom_length)) = Encoding::for_bom(bytes) {
+            let (content, _) = encoding.decode_without_bom_handling(&bytes[bom_length..]);
+            return (header::strip_bom(&content).to_string(), encoding);
+        }
+        
+        if let Ok(content) = std::str::from_utf8(bytes) {
+            return (header::strip_bom(content).to_string(), UTF_8);
+        }
         
-        let mut reader = BufReader::new(file);
-        let mut first_line = String::new();
+        // Not valid UTF-8, so assume Windows-1252 as written by most scoring software
+        let (content, _, _) = WINDOWS_1252.decode(bytes);
+        (content.into_owned(), WINDOWS_1252)
+    }
+
+    fn detect_delimiter(content: &str) -> char {
+        // Let the first few lines vote so a title row above the header can't decide it.
+        // Lines without any delimiter don't vote; ties go to the earlier candidate.
+        let mut votes = [0usize; 4];
         
-        if reader.read_line(&mut first_line).is_ok() {
-            if first_line.contains(';') {
-                return ';';
+        for line in content.lines().filter(|line| !line.trim().is_empty()).take(DELIMITER_SAMPLE_LINES) {
+            if let Some(i) = Self::detect_delimiter_in_line(line) {
+                votes[i] += 1;
             }
         }
         
-        ','  // Default to comma
+        Self::most_counted(&votes).map_or(',', |i| DELIMITERS[i])  // Default to comma
     }
 
-    fn get_columns_to_hide(headers: &[String]) -> Vec<&str> {
-        vec![
-            "sport_id", "team_members", "team_name",
-            "info", "result_code", "position_pre"
-        ]
+    // Index into DELIMITERS of the line's most frequent delimiter, if it has any
+    fn detect_delimiter_in_line(line: &str) -> Option<usize> {
+        let mut counts = [0usize; 4];
+        let mut in_quotes = false;
+        
+        // Skip anything inside a double-quoted span so quoted field
+        // contents don't count towards the delimiter
+        for c in line.chars() {
+            if c == '"' {
+                in_quotes = !in_quotes;
+            } else if !in_quotes {
+                if let Some(i) = DELIMITERS.iter().position(|d| *d == c) {
+                    counts[i] += 1;
+                }
+            }
+        }
+        
+        Self::most_counted(&counts)
     }
 
-    fn process_headers(
+    fn most_counted(counts: &[usize; 4]) -> Option<usize> {
+        let mut best = None;
+        let mut best_count = 0;
+        
+        for (i, &count) in counts.iter().enumerate() {
+            if count > best_count {
+                best = Some(i);
+                best_count = count;
+            }
+        }
+        
+        best
+    }
+
+    // Shared with the other file handlers. Every column keeps a header, hidden
+    // ones under their source name, along with whether it is shown
+    pub fn process_headers(
         headers: Vec<String>, 
-        columns_to_hide: &[&str]
+        columns: &ColumnConfig
     ) -> (Vec<String>, Vec<bool>) {
         let mut processed_headers = Vec::new();
         let mut visible_columns = Vec::new();
         
         for header in headers {
+            // Sheets and workbooks reach here without going through decode
+            let header = header::strip_bom(&header);
+            
             // Check if this column should be hidden
-            let should_hide = columns_to_hide.iter()
-                .any(|col| header.to_lowercase().contains(col));
+            let should_hide = columns.should_hide(header);
             
             visible_columns.push(!should_hide);
             
-            if !should_hide {
+            if should_hide {
+                processed_headers.push(header.trim().to_string());
+            } else {
                 // Apply header replacements
-                let processed_header = Self::replace_header(&header);
+                let processed_header = header::replace_header(header, &columns.replacements);
                 processed_headers.push(processed_header);
             }
         }
         
+        // Only the shown columns need telling apart on screen
+        let mut shown: Vec<String> = processed_headers.iter()
+            .zip(&visible_columns)
+            .filter(|(_, &visible)| visible)
+            .map(|(header, _)| header.clone())
+            .collect();
+        let collisions = header::disambiguate_headers(&mut shown);
+        if !collisions.is_empty() {
+            log::warn!("Renamed duplicate columns: {}", collisions.join(", "));
+        }
+        
+        let shown_headers = processed_headers.iter_mut()
+            .zip(&visible_columns)
+            .filter(|(_, &visible)| visible)
+            .map(|(header, _)| header);
+        for (header, renamed) in shown_headers.zip(shown) {
+            *header = renamed;
+        }
+        
         (processed_headers, visible_columns)
     }
+}
 
-    fn replace_header(header: &str) -> String {
-        let header_lower = header.to_lowercase();
-        
-        // Header replacements mapping
-        let replacements = [
-            ("category", "Series"),
-            ("first_name", "Name"),
-            ("last_name", "Surname"),
-            ("organization", "Club"),
-            ("napat", "X"),
-            ("result", "Result"),
-            ("posit.", "Rank")
-        ];
-        
-        // First check for part-X and psum-X patterns
-        if header_lower.contains("part-") {
-            if let Some(part_num) = header.split('-').nth(1) {
-                return format!("S{}", part_num);
-            }
-        } else if header_lower.contains("psum-") {
-            if let Some(part_num) = header.split('-').nth(1) {
-                return format!("P{}", part_num);
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_comma() {
+        assert_eq!(CSVHandler::detect_delimiter("name,club,result\nEero,Helsinki SC,624.3\n"), ',');
+    }
+
+    #[test]
+    fn detects_semicolon() {
+        assert_eq!(CSVHandler::detect_delimiter("name;club;result\nEero;Helsinki SC;624,3\n"), ';');
+    }
+
+    #[test]
+    fn detects_tab() {
+        assert_eq!(CSVHandler::detect_delimiter("name\tclub\tresult\nEero\tHelsinki SC\t624.3\n"), '\t');
+    }
+
+    #[test]
+    fn detects_pipe() {
+        assert_eq!(CSVHandler::detect_delimiter("name|club|result\nEero|Helsinki SC|624.3\n"), '|');
+    }
+
+    #[test]
+    fn defaults_to_comma_without_delimiters() {
+        assert_eq!(CSVHandler::detect_delimiter("result\n624.3\n"), ',');
+        assert_eq!(CSVHandler::detect_delimiter(""), ',');
+    }
+
+    #[test]
+    fn uses_first_non_empty_line() {
+        assert_eq!(CSVHandler::detect_delimiter("\n  \nname;club;result\n"), ';');
+    }
+
+    #[test]
+    fn title_without_delimiters_does_not_decide() {
+        let content = "Kevään SM\ncategory;first_name\nM;Eero\n";
+        assert_eq!(CSVHandler::detect_delimiter(content), ';');
         
-        // Then check other replacements
-        for (original, replacement) in replacements.iter() {
-            if header_lower.contains(original) {
-                return replacement.to_string();
-            }
-        }
+        let data = CSVHandler::parse_bytes(content.as_bytes(), &ColumnConfig::default()).unwrap();
+        assert_eq!(data.headers, vec!["Series", "Name"]);
+    }
+
+    #[test]
+    fn title_with_a_stray_comma_is_outvoted() {
+        let content = "Kevään SM, Lahti\ncategory;first_name\nM;Eero\nN;Aino\n";
+        assert_eq!(CSVHandler::detect_delimiter(content), ';');
+    }
+
+    #[test]
+    fn ignores_semicolons_inside_quotes() {
+        // Counted naively the semicolons would win two to one
+        let content = "\"Helsinki; SSG; Team A\",624.3\nEero,1\n";
+        assert_eq!(CSVHandler::detect_delimiter(content), ',');
+    }
+
+    #[test]
+    fn ignores_commas_inside_quotes() {
+        let content = "\"Laine, Eero\";\"Helsinki, SSG\";624,3\n";
+        assert_eq!(CSVHandler::detect_delimiter(content), ';');
+    }
+
+    #[test]
+    fn decodes_windows_1252() {
+        let bytes = include_bytes!("../tests/fixtures/windows-1252.csv");
+        let data = CSVHandler::parse_bytes(bytes, &ColumnConfig::default()).unwrap();
+        
+        assert_eq!(data.encoding, Some("windows-1252"));
+        assert_eq!(data.headers, vec!["sukunimi", "seura", "tulos"]);
+        assert_eq!(data.rows[0][0], "Pöllänen");
+        assert_eq!(data.rows[1][0], "Mäkelä");
+    }
+
+    #[test]
+    fn keeps_utf8_as_is() {
+        let data = CSVHandler::parse_bytes("name\nPöllänen\n".as_bytes(), &ColumnConfig::default()).unwrap();
+        
+        assert_eq!(data.encoding, Some("UTF-8"));
+        assert_eq!(data.rows[0][0], "Pöllänen");
+    }
+
+    #[test]
+    fn two_result_columns_get_distinct_names() {
+        let columns = ColumnConfig {
+            replacements: vec![("final".to_string(), "Result".to_string()), ("result".to_string(), "Result".to_string())],
+            ..ColumnConfig::default()
+        };
+        let headers = vec!["name".to_string(), "result".to_string(), "final".to_string()];
+        let (headers, _) = CSVHandler::process_headers(headers, &columns);
         
-        header.to_string()
+        assert_eq!(headers, vec!["name", "Result", "Result (2)"]);
+    }
+
+    #[test]
+    fn reads_gzip_like_plain_csv() {
+        let plain = include_bytes!("../tests/fixtures/results.csv");
+        let compressed = include_bytes!("../tests/fixtures/results.csv.gz");
+        let columns = ColumnConfig::default();
+        
+        let data = CSVHandler::parse_bytes(compressed, &columns).unwrap();
+        assert_eq!(data, CSVHandler::parse_bytes(plain, &columns).unwrap());
+        assert_eq!(data.rows.len(), 3);
+        assert_eq!(data.rows[1][2], "Mäkelä");
+    }
+
+    #[test]
+    fn strips_a_leading_bom() {
+        let data = CSVHandler::parse_bytes(b"\xef\xbb\xbfcategory,first_name\nM,Eero\n", &ColumnConfig::default()).unwrap();
+        assert_eq!(data.headers, vec!["Series", "Name"]);
+    }
+
+    #[test]
+    fn finds_the_marker_after_a_bom_and_title_rows() {
+        let data = CSVHandler::parse_bytes("\u{feff}Kevään SM;\ncategory;first_name\nM;Eero\n".as_bytes(), &ColumnConfig::default()).unwrap();
+        assert_eq!(data.headers, vec!["Series", "Name"]);
+        assert_eq!(data.title.as_deref(), Some("Kevään SM"));
     }
 }
@@ -1,16 +1,146 @@
 // src/data_types.rs
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone)]
+use crate::columns::parse_mapping;
+use crate::value::{format_time, parse_time};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DataSource {
     Local(PathBuf),
-    Cloud(String, String),  // (url, sheet_name)
+    Cloud(String, Vec<String>),  // (url, sheet_names)
+    Url(String),                 // Plain CSV download
+    Files(Vec<PathBuf>),         // Local CSVs with the same columns, shown as one table
+}
+
+impl DataSource {
+    // The files watched for changes, for local sources
+    pub fn local_files(&self) -> Option<Vec<PathBuf>> {
+        match self {
+            DataSource::Local(path) => Some(vec![path.clone()]),
+            DataSource::Files(paths) => Some(paths.clone()),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TableData {
     pub headers: Vec<String>,
     pub rows: Vec<Vec<String>>,
+    #[serde(skip)]
+    pub encoding: Option<&'static str>,  // Source encoding, for local files
+    #[serde(skip)]
+    pub column_types: Vec<ColumnType>,   // Inferred per column by infer_column_types
+    #[serde(skip)]
+    pub repaired_rows: usize,            // Short rows padded to the header length
+    #[serde(skip)]
+    pub dropped_rows: usize,             // Rows too short to be anything but a partial read
+    #[serde(default)]
+    pub totals: Vec<Vec<String>>,        // Trailing totals rows taken out of the results
+    #[serde(default)]
+    pub title: Option<String>,           // From the preamble rows above the header
+    #[serde(default)]
+    pub subtitle: Option<String>,
+    #[serde(default)]
+    pub key_columns: Vec<String>,        // Display names identifying a row, from the column config
+    #[serde(default)]
+    pub visible: Vec<bool>,              // Per column, false where the column config hides it; empty shows all
+    #[serde(skip)]
+    keys: Vec<String>,                   // Row keys of the full table, kept by visible_view and moved with the rows
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Text,
+    Integer,
+    Float,
+    Time,  // Shooting timings such as "10:05.3"
+}
+
+impl ColumnType {
+    // Numbers and times are right-aligned and compared by value
+    pub fn is_numeric(self) -> bool {
+        self != ColumnType::Text
+    }
+}
+
+// Header names recognised as the result column, including localised variants
+const RESULT_HEADERS: &[&str] = &["result", "score", "total", "tulos"];
+
+// Header names recognised as the rank column
+const RANK_HEADERS: &[&str] = &["rank", "sija"];
+
+// Header names recognised as the category or series column
+const CATEGORY_HEADERS: &[&str] = &["category", "class", "series", "sarja"];
+
+// Header names that identify a competitor, tried before falling back to the name
+const ID_HEADERS: &[&str] = &["id", "bib", "start_no", "nro"];
+const NAME_HEADERS: &[&str] = &["name", "surname"];
+
+// Header of the column added while comparing against a snapshot
+pub const DELTA_HEADER: &str = "±";
+
+// Parse a rank cell such as "1" or "1."
+pub fn parse_rank(cell: &str) -> Option<u32> {
+    cell.trim().trim_end_matches('.').parse::<u32>().ok()
+}
+
+// Parse a cell as a number, accepting a decimal comma as well as a point
+pub fn parse_number(cell: &str) -> Option<f64> {
+    cell.trim().replace(',', ".").parse::<f64>().ok()
+}
+
+// Parse a cell as a whole number
+pub fn parse_integer(cell: &str) -> Option<i64> {
+    cell.trim().parse::<i64>().ok()
+}
+
+// Aggregates of the result column for one category
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategorySummary {
+    pub category: String,
+    pub competitors: usize,
+    pub best: Option<String>,     // Formatted like the result column
+    pub average: Option<String>,
+}
+
+// How far a competitor has moved since the snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankDelta {
+    Up(u32),
+    Down(u32),
+    Same,
+    New,  // Not in the snapshot
+}
+
+impl fmt::Display for RankDelta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RankDelta::Up(places) => write!(f, "▲{}", places),
+            RankDelta::Down(places) => write!(f, "▼{}", places),
+            RankDelta::Same => write!(f, "—"),
+            RankDelta::New => write!(f, "new"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortDir {
+    Ascending,
+    Descending,
+}
+
+impl SortDir {
+    pub fn toggled(self) -> Self {
+        match self {
+            SortDir::Ascending => SortDir::Descending,
+            SortDir::Descending => SortDir::Ascending,
+        }
+    }
 }
 
 impl TableData {
@@ -18,6 +148,491 @@ impl TableData {
         TableData {
             headers: Vec::new(),
             rows: Vec::new(),
+            encoding: None,
+            column_types: Vec::new(),
+            repaired_rows: 0,
+            dropped_rows: 0,
+            totals: Vec::new(),
+            title: None,
+            subtitle: None,
+            key_columns: Vec::new(),
+            visible: Vec::new(),
+            keys: Vec::new(),
+        }
+    }
+
+    // Take the event title and subtitle from the first two non-empty preamble rows
+    pub fn set_preamble<'a, I, R>(&mut self, rows: I)
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = &'a str>,
+    {
+        let mut lines = rows.into_iter()
+            .map(|row| {
+                // Header mapping cells aren't part of the title
+                row.into_iter()
+                    .map(str::trim)
+                    .filter(|cell| !cell.is_empty() && parse_mapping(cell).is_none())
+                    .collect::<Vec<&str>>()
+                    .join(" — ")
+            })
+            .filter(|line| !line.is_empty());
+        
+        self.title = lines.next();
+        self.subtitle = lines.next();
+    }
+
+    // Pick each column's type from the kind most of its non-empty cells parse as
+    pub fn infer_column_types(&mut self) {
+        self.column_types = (0..self.headers.len())
+            .map(|column| {
+                let cells: Vec<&String> = self.rows.iter()
+                    .filter_map(|row| row.get(column))
+                    .filter(|cell| !cell.trim().is_empty())
+                    .collect();
+                let majority = |count: usize| !cells.is_empty() && count * 2 > cells.len();
+                
+                let integers = cells.iter().filter(|cell| parse_integer(cell).is_some()).count();
+                let numbers = cells.iter().filter(|cell| parse_number(cell).is_some()).count();
+                let times = cells.iter().filter(|cell| parse_time(cell).is_some()).count();
+                
+                if majority(integers) {
+                    ColumnType::Integer
+                } else if majority(numbers) {
+                    ColumnType::Float
+                } else if majority(times) {
+                    ColumnType::Time
+                } else {
+                    ColumnType::Text
+                }
+            })
+            .collect();
+    }
+
+    pub fn column_type(&self, column: usize) -> ColumnType {
+        self.column_types.get(column).copied().unwrap_or(ColumnType::Text)
+    }
+
+    // The numeric value of a cell according to its column type
+    pub fn value(&self, row: &[String], column: usize) -> Option<f64> {
+        let cell = row.get(column)?;
+        match self.column_type(column) {
+            ColumnType::Integer | ColumnType::Float => parse_number(cell),
+            ColumnType::Time => parse_time(cell),
+            ColumnType::Text => None,
+        }
+    }
+
+    // Whether the column is shown; hidden ones are kept for rules and export
+    pub fn is_visible(&self, column: usize) -> bool {
+        self.visible.get(column).copied().unwrap_or(true)
+    }
+
+    pub fn has_hidden_columns(&self) -> bool {
+        self.visible.iter().any(|visible| !visible)
+    }
+
+    // Index of the first shown column whose header matches, ignoring case and surrounding whitespace
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        (0..self.headers.len())
+            .find(|&column| self.is_visible(column) && self.headers[column].trim().eq_ignore_ascii_case(name))
+    }
+
+    // Index of the first column matching any of the names, in order of preference
+    pub fn column_index_any(&self, names: &[&str]) -> Option<usize> {
+        names.iter().find_map(|name| self.column_index(name))
+    }
+
+    // Like column_index, but falling back to hidden columns
+    fn any_column_index(&self, name: &str) -> Option<usize> {
+        self.column_index(name)
+            .or_else(|| self.headers.iter().position(|h| h.trim().eq_ignore_ascii_case(name)))
+    }
+
+    // A cell of the row at an index by column name, shown or hidden, with its numeric value
+    pub fn named_cell(&self, row_index: usize, name: &str) -> Option<(&str, Option<f64>)> {
+        let row = self.rows.get(row_index)?;
+        let column = self.any_column_index(name)?;
+        row.get(column).map(|cell| (cell.as_str(), self.value(row, column)))
+    }
+
+    // The table as displayed: only the shown columns, rows in the same order
+    pub fn visible_view(&self) -> TableData {
+        let columns: Vec<usize> = (0..self.headers.len()).filter(|&column| self.is_visible(column)).collect();
+        let project = |row: &Vec<String>| -> Vec<String> {
+            columns.iter().filter_map(|&column| row.get(column).cloned()).collect()
+        };
+        
+        TableData {
+            headers: columns.iter().map(|&column| self.headers[column].clone()).collect(),
+            rows: self.rows.iter().map(&project).collect(),
+            column_types: columns.iter().map(|&column| self.column_type(column)).collect(),
+            totals: self.totals.iter().map(&project).collect(),
+            visible: Vec::new(),
+            encoding: self.encoding,
+            repaired_rows: self.repaired_rows,
+            dropped_rows: self.dropped_rows,
+            title: self.title.clone(),
+            subtitle: self.subtitle.clone(),
+            key_columns: self.key_columns.clone(),
+            keys: self.row_keys(),
+        }
+    }
+
+    // A copy with only the rows at the given indices, in that order, each keeping its key
+    pub fn select_rows(&self, indices: &[usize]) -> TableData {
+        let keys = self.row_keys();
+        let picked: Vec<usize> = indices.iter().copied().filter(|&index| index < self.rows.len()).collect();
+        
+        TableData {
+            rows: picked.iter().map(|&index| self.rows[index].clone()).collect(),
+            keys: picked.iter().map(|&index| keys[index].clone()).collect(),
+            ..self.clone()
         }
     }
+
+    // The column holding the final result, matched on the displayed header names
+    pub fn result_column(&self) -> Option<usize> {
+        self.column_index_any(RESULT_HEADERS)
+    }
+
+    // A key identifying the competitor on the row at an index, stable across
+    // refreshes: the configured key columns, else an ID column, else the name
+    // columns, else the first cell. Hidden columns count, so a hidden ID can be
+    // the key. Empty past the end.
+    pub fn row_key(&self, row_index: usize) -> String {
+        if self.keys.len() == self.rows.len() {
+            return self.keys.get(row_index).cloned().unwrap_or_default();
+        }
+        self.rows.get(row_index)
+            .map(|row| Self::key_of(&self.key_column_indices(), row))
+            .unwrap_or_default()
+    }
+
+    // The keys of every row, in order
+    pub fn row_keys(&self) -> Vec<String> {
+        // A projection keeps the keys of the table it was taken from
+        if self.keys.len() == self.rows.len() {
+            return self.keys.clone();
+        }
+        let columns = self.key_column_indices();
+        self.rows.iter().map(|row| Self::key_of(&columns, row)).collect()
+    }
+
+    fn key_column_indices(&self) -> Vec<usize> {
+        let configured: Vec<usize> = self.key_columns.iter()
+            .filter_map(|name| self.any_column_index(name.trim()))
+            .collect();
+        if !configured.is_empty() {
+            return configured;
+        }
+        match ID_HEADERS.iter().find_map(|name| self.any_column_index(name)) {
+            Some(id) => vec![id],
+            None => NAME_HEADERS.iter().filter_map(|name| self.any_column_index(name)).collect(),
+        }
+    }
+
+    fn key_of(columns: &[usize], row: &[String]) -> String {
+        if columns.is_empty() {
+            return row.first().cloned().unwrap_or_default();
+        }
+        columns.iter()
+            .map(|&i| row.get(i).map(String::as_str).unwrap_or_default())
+            .collect::<Vec<&str>>()
+            .join("\u{1f}")
+    }
+
+    pub fn category_column(&self) -> Option<usize> {
+        self.column_index_any(CATEGORY_HEADERS)
+    }
+
+    // Competitor count, best and average result per category, in order of first
+    // appearance. Empty when the table has no category or result column.
+    pub fn category_summaries(&self) -> Vec<CategorySummary> {
+        let (category, result) = match (self.category_column(), self.result_column()) {
+            (Some(category), Some(result)) => (category, result),
+            _ => return Vec::new(),
+        };
+        
+        // Times are best when lowest, scores when highest
+        let is_time = self.column_type(result) == ColumnType::Time;
+        let format = |value: f64| match self.column_type(result) {
+            ColumnType::Time => format_time(value),
+            ColumnType::Integer => format!("{:.0}", value),
+            _ => format!("{:.2}", value),
+        };
+        
+        let mut groups: Vec<(String, usize, Vec<f64>)> = Vec::new();
+        for row in self.rows.iter() {
+            let name = row.get(category).map(|cell| cell.trim()).unwrap_or_default();
+            let index = match groups.iter().position(|(group, _, _)| group == name) {
+                Some(index) => index,
+                None => {
+                    groups.push((name.to_string(), 0, Vec::new()));
+                    groups.len() - 1
+                }
+            };
+            
+            groups[index].1 += 1;
+            groups[index].2.extend(self.value(row, result));
+        }
+        
+        groups.into_iter()
+            .map(|(category, competitors, values)| {
+                let best = values.iter().copied().reduce(|a, b| {
+                    if is_time { a.min(b) } else { a.max(b) }
+                });
+                let average = (!values.is_empty())
+                    .then(|| values.iter().sum::<f64>() / values.len() as f64);
+                
+                CategorySummary {
+                    category,
+                    competitors,
+                    best: best.map(format),
+                    // Averages of whole-number scores need the fraction
+                    average: average.map(|value| if is_time { format_time(value) } else { format!("{:.2}", value) }),
+                }
+            })
+            .collect()
+    }
+
+    // The different non-empty values of a column, for the facet filter. Values
+    // differing only in case are one entry, shown as first spelled.
+    pub fn distinct_values(&self, column: usize) -> Vec<String> {
+        let mut values: Vec<String> = self.rows.iter()
+            .filter_map(|row| row.get(column))
+            .map(|cell| cell.trim().to_string())
+            .filter(|cell| !cell.is_empty())
+            .collect();
+        values.sort_by_key(|value| value.to_lowercase());
+        values.dedup_by_key(|value| value.to_lowercase());
+        values
+    }
+
+    // The column holding the placing, used for medal highlighting
+    pub fn rank_column(&self) -> Option<usize> {
+        self.column_index_any(RANK_HEADERS)
+    }
+
+    pub fn numeric_columns(&self) -> Vec<bool> {
+        (0..self.headers.len())
+            .map(|column| self.column_type(column).is_numeric())
+            .collect()
+    }
+
+    // Stable sort of the rows by one column, by value for number and time columns
+    pub fn sorted_rows(&self, column: usize, dir: SortDir) -> Vec<&Vec<String>> {
+        let mut rows: Vec<&Vec<String>> = self.rows.iter().collect();
+        let cell = |row: &Vec<String>| row.get(column).map(|c| c.trim().to_string()).unwrap_or_default();
+        
+        let numeric = self.column_type(column).is_numeric();
+        
+        let directed = |ordering: Ordering| match dir {
+            SortDir::Ascending => ordering,
+            SortDir::Descending => ordering.reverse(),
+        };
+        
+        rows.sort_by(|a, b| {
+            if numeric {
+                // Empty cells and cells of another kind stay at the end in either direction
+                match (self.value(a, column), self.value(b, column)) {
+                    (Some(x), Some(y)) => directed(x.partial_cmp(&y).unwrap_or(Ordering::Equal)),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            } else {
+                directed(cell(a).to_lowercase().cmp(&cell(b).to_lowercase()))
+            }
+        });
+        
+        rows
+    }
+
+    // Each competitor's placing, from the rank column or else the row order.
+    // Competitors without a placing (DNF, DNS) are left out.
+    pub fn ranks(&self) -> HashMap<String, u32> {
+        let rank_column = self.rank_column();
+        let mut ranks = HashMap::new();
+        for (position, (row, key)) in self.rows.iter().zip(self.row_keys()).enumerate() {
+            let rank = match rank_column {
+                Some(column) => row.get(column).and_then(|cell| parse_rank(cell)),
+                None => Some(position as u32 + 1),
+            };
+            if let Some(rank) = rank {
+                ranks.entry(key).or_insert(rank);
+            }
+        }
+        ranks
+    }
+
+    // Movement of every row since the snapshot, None for rows without a placing
+    pub fn rank_deltas(&self, snapshot: &TableData) -> Vec<Option<RankDelta>> {
+        let (before, now) = (snapshot.ranks(), self.ranks());
+        self.row_keys().into_iter()
+            .map(|key| {
+                let rank = *now.get(&key)?;
+                Some(match before.get(&key) {
+                    None => RankDelta::New,
+                    Some(&old) if old > rank => RankDelta::Up(old - rank),
+                    Some(&old) if old < rank => RankDelta::Down(rank - old),
+                    Some(_) => RankDelta::Same,
+                })
+            })
+            .collect()
+    }
+
+    // This table with a column of rank changes since the snapshot appended
+    pub fn with_rank_deltas(&self, snapshot: &TableData) -> TableData {
+        let mut compared = self.clone();
+        for (row, delta) in compared.rows.iter_mut().zip(self.rank_deltas(snapshot)) {
+            row.resize(self.headers.len(), String::new());
+            row.push(delta.map(|delta| delta.to_string()).unwrap_or_default());
+        }
+        compared.headers.push(DELTA_HEADER.to_string());
+        compared.column_types.resize(self.headers.len(), ColumnType::Text);
+        compared.column_types.push(ColumnType::Text);
+        if !compared.visible.is_empty() {
+            compared.visible.resize(self.headers.len(), true);
+            compared.visible.push(true);
+        }
+        compared
+    }
+
+    // Competitors in the snapshot who are no longer listed
+    pub fn missing_since(&self, snapshot: &TableData) -> usize {
+        let now: HashSet<String> = self.row_keys().into_iter().collect();
+        let mut gone: Vec<String> = snapshot.row_keys().into_iter()
+            .filter(|key| !now.contains(key))
+            .collect();
+        gone.sort();
+        gone.dedup();
+        gone.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::columns::ColumnConfig;
+    use crate::csv_handler::CSVHandler;
+
+    fn table(headers: &[&str], rows: &[&[&str]]) -> TableData {
+        let mut data = TableData::empty();
+        data.headers = headers.iter().map(|header| header.to_string()).collect();
+        data.rows = rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        data.infer_column_types();
+        data
+    }
+
+    #[test]
+    fn finds_result_column_after_replacement() {
+        let (headers, _) = CSVHandler::process_headers(
+            vec!["first_name".to_string(), "result".to_string(), "posit.".to_string()],
+            &ColumnConfig::default(),
+        );
+        let data = table(&headers.iter().map(String::as_str).collect::<Vec<&str>>(), &[]);
+        
+        assert_eq!(data.headers, vec!["Name", "Result", "Rank"]);
+        assert_eq!(data.result_column(), Some(1));
+        assert_eq!(data.rank_column(), Some(2));
+    }
+
+    #[test]
+    fn finds_result_aliases() {
+        assert_eq!(table(&["Name", "Tulos"], &[]).result_column(), Some(1));
+        assert_eq!(table(&["Name", "Score"], &[]).result_column(), Some(1));
+        assert_eq!(table(&["Sija", "Name", "Total"], &[]).rank_column(), Some(0));
+        assert_eq!(table(&["Name", "Club"], &[]).result_column(), None);
+    }
+
+    #[test]
+    fn sorts_time_column_by_value_with_other_cells_last() {
+        let data = table(&["Name", "Time"], &[&["A", "10:00.0"], &["B", "DNF"], &["C", "9:59.9"]]);
+        assert_eq!(data.column_type(1), ColumnType::Time);
+        
+        let names = |dir| data.sorted_rows(1, dir).iter().map(|row| row[0].clone()).collect::<Vec<String>>();
+        assert_eq!(names(SortDir::Ascending), vec!["C", "A", "B"]);
+        assert_eq!(names(SortDir::Descending), vec!["A", "C", "B"]);
+    }
+
+    #[test]
+    fn distinct_values_ignore_case() {
+        let data = table(&["Club"], &[&["team a"], &["Team B"], &["Team A"], &[" team a "], &[""]]);
+        assert_eq!(data.distinct_values(0), vec!["team a", "Team B"]);
+    }
+
+    #[test]
+    fn row_key_uses_a_configured_column() {
+        let mut data = table(&["Name", "Club", "Bib"], &[&["Eero", "HSC", "7"], &["Aino", "LA", "12"]]);
+        data.key_columns = vec!["club".to_string()];
+        
+        assert_eq!(data.row_key(0), "HSC");
+        assert_eq!(data.row_key(1), "LA");
+        assert_eq!(data.row_key(2), "");
+    }
+
+    #[test]
+    fn row_key_joins_several_columns() {
+        let mut data = table(&["Name", "Surname", "Club"], &[&["Eero", "Laine", "HSC"]]);
+        data.key_columns = vec!["Name".to_string(), "Club".to_string()];
+        
+        assert_eq!(data.row_key(0), "Eero\u{1f}HSC");
+    }
+
+    #[test]
+    fn row_key_falls_back_to_id_then_names_then_the_first_cell() {
+        let with_id = table(&["Name", "Bib"], &[&["Eero", "7"]]);
+        assert_eq!(with_id.row_key(0), "7");
+        
+        let with_names = table(&["Club", "Name", "Surname"], &[&["HSC", "Eero", "Laine"]]);
+        assert_eq!(with_names.row_key(0), "Eero\u{1f}Laine");
+        
+        let plain = table(&["Team", "Points"], &[&["Lahti", "98"]]);
+        assert_eq!(plain.row_key(0), "Lahti");
+    }
+
+    #[test]
+    fn row_key_can_use_a_hidden_column() {
+        let mut data = table(&["id", "Name"], &[&["101", "Eero"], &["102", "Eero"]]);
+        data.visible = vec![false, true];
+        
+        // Same name twice, told apart by the hidden ID, on the table and its projection
+        assert_eq!(data.row_keys(), vec!["101", "102"]);
+        assert_eq!(data.visible_view().row_keys(), vec!["101", "102"]);
+    }
+
+    #[test]
+    fn selected_rows_keep_their_own_keys() {
+        let mut data = table(&["id", "Name"], &[&["101", "Eero"], &["102", "Eero"], &["103", "Aino"]]);
+        data.visible = vec![false, true];
+        
+        // Reordered and cut, the hidden IDs still follow their rows
+        let picked = data.visible_view().select_rows(&[2, 1]);
+        assert_eq!(picked.rows, vec![vec!["Aino"], vec!["Eero"]]);
+        assert_eq!(picked.row_keys(), vec!["103", "102"]);
+    }
+
+    #[test]
+    fn visible_view_drops_hidden_columns_only() {
+        let mut data = table(&["Name", "sport_id", "Result"], &[&["Eero", "3", "624.3"], &["Aino", "3", "618.0"]]);
+        data.visible = vec![true, false, true];
+        
+        let shown = data.visible_view();
+        assert_eq!(shown.headers, vec!["Name", "Result"]);
+        assert_eq!(shown.rows, vec![vec!["Eero", "624.3"], vec!["Aino", "618.0"]]);
+        assert_eq!(shown.column_types, vec![ColumnType::Text, ColumnType::Float]);
+        assert!(!shown.has_hidden_columns());
+    }
+
+    #[test]
+    fn hidden_columns_stay_addressable_by_name() {
+        let mut data = table(&["Name", "sport_id"], &[&["Eero", "3"]]);
+        data.visible = vec![true, false];
+        
+        assert_eq!(data.column_index("sport_id"), None);
+        assert_eq!(data.named_cell(0, "sport_id"), Some(("3", Some(3.0))));
+    }
 }
@@ -0,0 +1,150 @@
+// src/export.rs
+use fontdue::{Font, FontSettings};
+use iced::Color;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use tiny_skia::{ColorU8, Paint, Pixmap, PixmapPaint, Rect, Transform};
+
+use crate::data_types::{parse_rank, TableData};
+use crate::fonts::BUNDLED_FONT;
+use crate::ui::Styles;
+
+// Fonts tried in order when rendering text offscreen, before the bundled one
+const FONT_PATHS: &[&str] = &[
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    "/Library/Fonts/Arial.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+];
+
+const CAPTION_HEIGHT: f32 = 80.0;
+const PADDING: f32 = 10.0;
+
+// Render the table to a PNG, sized for posting on social media
+pub fn export_png(
+    data: &TableData,
+    theme: &Styles,
+    caption: &str,
+    width: u32,
+    height: u32,
+    path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let font = load_font().ok_or("no usable font found for image export")?;
+    let mut pixmap = Pixmap::new(width, height).ok_or("invalid image size")?;
+    pixmap.fill(to_skia(theme.bg));
+
+    let result_column = data.result_column();
+    let rank_column = data.rank_column();
+
+    // Caption bar with the event title and timestamp
+    fill_rect(&mut pixmap, 0.0, 0.0, width as f32, CAPTION_HEIGHT, theme.footer_bg);
+    draw_text(&mut pixmap, &font, caption, PADDING, CAPTION_HEIGHT * 0.3, 32.0, theme.footer_fg);
+
+    // Fit the header and every row into the remaining height
+    let row_count = data.rows.len() + 1;
+    let row_height = ((height as f32 - CAPTION_HEIGHT) / row_count as f32).min(48.0);
+    let text_size = row_height * 0.6;
+
+    // First column is wider, like on screen
+    let columns = data.headers.len().max(1) as f32;
+    let column_width = width as f32 / (columns + 0.5);
+    let column_x = |i: usize| if i == 0 { 0.0 } else { column_width * (i as f32 + 0.5) };
+
+    let mut y = CAPTION_HEIGHT;
+    fill_rect(&mut pixmap, 0.0, y, width as f32, row_height, theme.header_bg);
+    for (i, header) in data.headers.iter().enumerate() {
+        draw_text(&mut pixmap, &font, header, column_x(i) + PADDING, y + row_height * 0.2, text_size, theme.header_fg);
+    }
+    y += row_height;
+
+    for row in data.rows.iter() {
+        let medal = rank_column
+            .and_then(|idx| row.get(idx))
+            .and_then(|rank| parse_rank(rank))
+            .and_then(|rank| match rank {
+                1 => Some(theme.gold),
+                2 => Some(theme.silver),
+                3 => Some(theme.bronze),
+                _ => None,
+            });
+
+        if let Some(color) = medal {
+            fill_rect(&mut pixmap, 0.0, y, width as f32, row_height, color);
+        }
+
+        for (i, cell) in row.iter().enumerate() {
+            let is_result_column = result_column == Some(i);
+            if is_result_column && medal.is_none() {
+                let cell_width = if i == 0 { column_width * 1.5 } else { column_width };
+                fill_rect(&mut pixmap, column_x(i), y, cell_width, row_height, theme.result_bg);
+            }
+
+            let color = if is_result_column { theme.result_fg } else { theme.fg };
+            draw_text(&mut pixmap, &font, cell, column_x(i) + PADDING, y + row_height * 0.2, text_size, color);
+        }
+
+        y += row_height;
+    }
+
+    pixmap.save_png(path)?;
+    Ok(())
+}
+
+fn load_font() -> Option<Font> {
+    FONT_PATHS.iter()
+        .filter_map(|path| fs::read(path).ok())
+        .find_map(|bytes| Font::from_bytes(bytes, FontSettings::default()).ok())
+        .or_else(|| Font::from_bytes(BUNDLED_FONT, FontSettings::default()).ok())
+}
+
+fn to_skia(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba(color.r, color.g, color.b, color.a)
+        .unwrap_or(tiny_skia::Color::BLACK)
+}
+
+fn fill_rect(pixmap: &mut Pixmap, x: f32, y: f32, width: f32, height: f32, color: Color) {
+    if let Some(rect) = Rect::from_xywh(x, y, width, height) {
+        let mut paint = Paint::default();
+        paint.set_color(to_skia(color));
+        pixmap.fill_rect(rect, &paint, Transform::identity(), None);
+    }
+}
+
+fn draw_text(pixmap: &mut Pixmap, font: &Font, text: &str, x: f32, y: f32, size: f32, color: Color) {
+    let mut pen_x = x;
+    let baseline = y + size;
+
+    for c in text.chars() {
+        let (metrics, coverage) = font.rasterize(c, size);
+
+        // Turn the coverage mask into a tinted glyph image and blend it in
+        if metrics.width > 0 && metrics.height > 0 {
+            if let Some(mut glyph) = Pixmap::new(metrics.width as u32, metrics.height as u32) {
+                for (pixel, alpha) in glyph.pixels_mut().iter_mut().zip(coverage.iter()) {
+                    let a = (*alpha as f32 / 255.0) * color.a;
+                    *pixel = ColorU8::from_rgba(
+                        (color.r * 255.0) as u8,
+                        (color.g * 255.0) as u8,
+                        (color.b * 255.0) as u8,
+                        (a * 255.0) as u8,
+                    ).premultiply();
+                }
+
+                let glyph_x = pen_x + metrics.xmin as f32;
+                let glyph_y = baseline - metrics.height as f32 - metrics.ymin as f32;
+                pixmap.draw_pixmap(
+                    glyph_x as i32,
+                    glyph_y as i32,
+                    glyph.as_ref(),
+                    &PixmapPaint::default(),
+                    Transform::identity(),
+                    None,
+                );
+            }
+        }
+
+        pen_x += metrics.advance_width;
+    }
+}
@@ -0,0 +1,57 @@
+// src/fonts.rs
+use iced::Font;
+use std::borrow::Cow;
+use std::fs;
+
+use crate::config::AppConfig;
+
+// DejaVu Sans covers ä/ö and the symbols used in the labels, unlike iced's default font
+pub const BUNDLED_FONT: &[u8] = include_bytes!("../assets/fonts/DejaVuSans.ttf");
+const BUNDLED_FAMILY: &str = "DejaVu Sans";
+
+// Bytes of the UI font: the configured file if it can be read, otherwise the bundled one
+pub fn font_bytes(config: &AppConfig) -> Cow<'static, [u8]> {
+    match (&config.font_path, &config.font_family) {
+        (Some(path), Some(_)) => match fs::read(path) {
+            Ok(bytes) => Cow::Owned(bytes),
+            Err(_) => Cow::Borrowed(BUNDLED_FONT),
+        },
+        _ => Cow::Borrowed(BUNDLED_FONT),
+    }
+}
+
+// The font iced should use by default, matching whatever font_bytes returns
+pub fn default_font(config: &AppConfig) -> Font {
+    match (&config.font_path, &config.font_family) {
+        (Some(path), Some(family)) if path.exists() => {
+            // iced wants a 'static family name; this runs once at startup
+            Font::with_name(Box::leak(family.clone().into_boxed_str()))
+        }
+        _ => Font::with_name(BUNDLED_FAMILY),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_font_loads_with_finnish_glyphs() {
+        let font = fontdue::Font::from_bytes(BUNDLED_FONT, fontdue::FontSettings::default()).unwrap();
+        for glyph in ['ä', 'ö', 'Ä', 'Ö', '▲', '▼'] {
+            assert_ne!(font.lookup_glyph_index(glyph), 0, "no glyph for {}", glyph);
+        }
+    }
+
+    #[test]
+    fn falls_back_to_the_bundled_font() {
+        let config = AppConfig {
+            font_path: Some("no/such/font.ttf".into()),
+            font_family: Some("Missing Sans".to_string()),
+            ..AppConfig::default()
+        };
+        
+        assert_eq!(font_bytes(&config).as_ref(), BUNDLED_FONT);
+        assert_eq!(default_font(&config), Font::with_name(BUNDLED_FAMILY));
+    }
+}
@@ -0,0 +1,131 @@
+// src/header.rs
+
+// Built-in header replacements, checked in order
+pub const DEFAULT_REPLACEMENTS: &[(&str, &str)] = &[
+    ("category", "Series"),
+    ("first_name", "Name"),
+    ("last_name", "Surname"),
+    ("organization", "Club"),
+    ("napat", "X"),
+    ("result", "Result"),
+    ("posit.", "Rank"),
+];
+
+// A byte order mark some exporters leave in the first cell, or repeat at the
+// start of the text after the real one was consumed
+const BOM: char = '\u{feff}';
+
+pub fn strip_bom(cell: &str) -> &str {
+    cell.trim_start_matches(BOM)
+}
+
+// Number repeated display names, e.g. two columns mapping to "Result" become
+// "Result" and "Result (2)", so every column can be told apart by its header.
+// Returns the names that collided.
+pub fn disambiguate_headers(headers: &mut [String]) -> Vec<String> {
+    let mut collisions = Vec::new();
+    
+    for i in 1..headers.len() {
+        let name = headers[i].clone();
+        let taken = |candidate: &str, headers: &[String]| {
+            headers.iter().enumerate().any(|(j, other)| j != i && other.trim().eq_ignore_ascii_case(candidate.trim()))
+        };
+        if !headers[..i].iter().any(|other| other.trim().eq_ignore_ascii_case(name.trim())) {
+            continue;
+        }
+        
+        // Skip numbers already used by another column
+        let mut n = 2;
+        while taken(&format!("{} ({})", name, n), headers) {
+            n += 1;
+        }
+        headers[i] = format!("{} ({})", name, n);
+        
+        if !collisions.contains(&name) {
+            collisions.push(name);
+        }
+    }
+    
+    collisions
+}
+
+pub fn replace_header(header: &str, replacements: &[(String, String)]) -> String {
+    let header_lower = header.to_lowercase();
+    
+    // First check for part-X and psum-X patterns
+    if header_lower.contains("part-") {
+        if let Some(part_num) = header.split('-').nth(1) {
+            return format!("S{}", part_num);
+        }
+    } else if header_lower.contains("psum-") {
+        if let Some(part_num) = header.split('-').nth(1) {
+            return format!("P{}", part_num);
+        }
+    }
+    
+    // Then check other replacements
+    for (original, replacement) in replacements.iter() {
+        if header_lower.contains(&original.to_lowercase()) {
+            return replacement.to_string();
+        }
+    }
+    
+    header.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults() -> Vec<(String, String)> {
+        DEFAULT_REPLACEMENTS.iter()
+            .map(|(original, replacement)| (original.to_string(), replacement.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn part_becomes_stage() {
+        assert_eq!(replace_header("part-3", &defaults()), "S3");
+        assert_eq!(replace_header("Part-12", &defaults()), "S12");
+    }
+
+    #[test]
+    fn psum_becomes_running_total() {
+        assert_eq!(replace_header("psum-2", &defaults()), "P2");
+    }
+
+    #[test]
+    fn replaces_known_headers() {
+        assert_eq!(replace_header("category", &defaults()), "Series");
+        assert_eq!(replace_header("posit.", &defaults()), "Rank");
+    }
+
+    #[test]
+    fn keeps_unknown_headers() {
+        assert_eq!(replace_header("Club notes", &defaults()), "Club notes");
+    }
+
+    #[test]
+    fn numbers_duplicate_headers() {
+        let mut headers = vec!["Result".to_string(), "Name".to_string(), "result".to_string(), "Result".to_string()];
+        let collisions = disambiguate_headers(&mut headers);
+        
+        assert_eq!(headers, vec!["Result", "Name", "result (2)", "Result (3)"]);
+        assert_eq!(collisions, vec!["result", "Result"]);
+    }
+
+    #[test]
+    fn skips_numbers_already_in_use() {
+        let mut headers = vec!["Result".to_string(), "Result".to_string(), "Result (2)".to_string()];
+        disambiguate_headers(&mut headers);
+        
+        assert_eq!(headers, vec!["Result", "Result (3)", "Result (2)"]);
+    }
+
+    #[test]
+    fn leaves_distinct_headers_alone() {
+        let mut headers = vec!["Name".to_string(), "Result".to_string()];
+        assert!(disambiguate_headers(&mut headers).is_empty());
+        assert_eq!(headers, vec!["Name", "Result"]);
+    }
+}
@@ -0,0 +1,61 @@
+// src/labels.rs
+// User-facing strings, kept in one place so they stay correctly encoded.
+// The footer uses words rather than emoji, which the default font can't draw on Windows.
+
+pub const COPYRIGHT: &str = "Score Viewer © 2024-2025 Joona Holkko. All rights reserved.";
+
+// Footer buttons
+pub const REFRESH: &str = "Refresh";
+pub const RECONNECT: &str = "Reconnect";
+pub const AUTO_SCROLL_START: &str = "▶ Scroll";
+pub const AUTO_SCROLL_STOP: &str = "■ Stop";
+pub const DENSITY_COMPACT: &str = "Compact";
+pub const DENSITY_COMFORTABLE: &str = "Comfortable";
+pub const EXPORT: &str = "Snapshot";
+pub const CLOUD: &str = "Cloud";
+pub const LOCAL: &str = "Local";
+pub const INFO: &str = "Info";
+pub const SETTINGS: &str = "Settings";
+pub const SUMMARY: &str = "Summary";
+pub const COMPARE: &str = "Compare";
+pub const COMPARE_CLEAR: &str = "Clear compare";
+pub const CLOCK: &str = "Clock";
+pub const THEME: &str = "Theme";
+pub const QUIT: &str = "Quit";
+pub const PLAYLIST_PAUSE: &str = "Hold";
+pub const PLAYLIST_RESUME: &str = "Rotate";
+pub const PLAYLIST_NEXT: &str = "Next";
+pub const PAUSE_UPDATES: &str = "Pause updates";
+pub const RESUME_UPDATES: &str = "Resume updates";
+pub const CLEAR_SEARCH: &str = "✕";
+pub const SEARCH_PLACEHOLDER: &str = "Search...";
+
+// Placeholders when there is no table to show
+pub const NO_SOURCE: &str = "Select a source to begin: open a local file or connect to an online sheet.";
+pub const EMPTY_SHEET: &str = "Connected, but the sheet has no rows yet.";
+pub const LOADING: &str = "Loading results...";
+pub const NOT_LOADED: &str = "No data loaded yet.";
+
+// Summary panel
+pub const SUMMARY_COMPETITORS: &str = "Competitors";
+pub const SUMMARY_BEST: &str = "Best";
+pub const SUMMARY_AVERAGE: &str = "Average";
+pub const SUMMARY_TOTALS: &str = "Totals";
+
+// Clock overlay
+pub const COUNTDOWN_OVER: &str = "TIME";
+
+// Row limit
+pub const ALL_ROWS: &str = "All rows";
+pub const TOP_ROWS: &str = "Top";
+pub const MORE_ROWS: &str = "more";
+
+// Snapshot comparison
+pub const COMPARED_WITH: &str = "compared with";
+pub const NO_LONGER_LISTED: &str = "no longer listed";
+
+// Refresh status
+pub const STALE: &str = "⚠ Stale since";
+pub const UPDATED: &str = "Updated";
+pub const REFRESHING: &str = " (refreshing...)";
+pub const PAUSED: &str = "⏸ Paused";
@@ -0,0 +1,33 @@
+// src/logging.rs
+use std::env;
+use std::fs::OpenOptions;
+
+// SVR_LOG sets the level or filter ("debug", "score_viewer=trace"), SVR_LOG_FILE
+// appends to a file instead of stderr so a venue PC keeps a record
+const LEVEL_ENV: &str = "SVR_LOG";
+const FILE_ENV: &str = "SVR_LOG_FILE";
+const DEFAULT_LEVEL: &str = "info";
+
+// Start the logger, falling back to stderr when the log file can't be opened
+pub fn init() {
+    let mut builder = env_logger::Builder::new();
+    builder.parse_filters(&env::var(LEVEL_ENV).unwrap_or_else(|_| DEFAULT_LEVEL.to_string()));
+
+    let mut file_error = None;
+    if let Some(path) = env::var_os(FILE_ENV) {
+        match OpenOptions::new().create(true).append(true).open(&path) {
+            Ok(file) => {
+                builder.target(env_logger::Target::Pipe(Box::new(file)));
+            }
+            Err(err) => file_error = Some(format!("can't open log file {:?}: {}", path, err)),
+        }
+    }
+
+    // A second init (e.g. from the CLI path) is harmless
+    if builder.try_init().is_ok() {
+        if let Some(err) = file_error {
+            log::warn!("{}", err);
+        }
+        log::info!("Score Viewer {} starting", env!("CARGO_PKG_VERSION"));
+    }
+}
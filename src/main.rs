@@ -1,44 +1,140 @@
 // src/main.rs
 use iced::{
     alignment, executor, Application, Command, Container, Element, Length, Settings, 
-    Subscription, Theme, Color, Background, Text, Row, Column, Button, Scrollable, Space,
+    Subscription, Theme, Color, Background, Font, font, Text, Row, Column, Button, Scrollable, Space,
     alignment::Horizontal, window
 };
-use iced::widget::{button, column, container, row, scrollable, text};
+use iced::widget::{button, checkbox, column, container, image, pick_list, row, scrollable, text, text_input, tooltip};
+use iced::keyboard::{self, KeyCode};
+use iced::{event, Event};
 use once_cell::sync::Lazy;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
 use std::time::{Duration, Instant};
 use std::sync::{Arc, Mutex};
 use std::fs;
-use chrono::Local;
+use std::fmt;
+use chrono::{DateTime, Local, Timelike};
 use rfd::FileDialog;
 
 mod csv_handler;
+mod cache;
+mod cli;
+mod clock;
 mod cloud_handler;
+mod columns;
+mod config;
 mod data_types;
+mod export;
+mod fonts;
+mod header;
+mod labels;
+mod logging;
+mod modal;
+mod playlist;
 mod ui;
+mod value;
+mod url_handler;
+mod xlsx_handler;
 
 use csv_handler::CSVHandler;
-use cloud_handler::CloudHandler;
-use data_types::{TableData, DataSource};
-use ui::{Styles, DARK_THEME, LIGHT_THEME};
+use cloud_handler::{unknown_sheets, CloudClient, CloudHandler, FetchError, MockCloudHandler, DEMO_URL};
+use url_handler::{UrlFetch, UrlHandler, Validators};
+use xlsx_handler::XlsxHandler;
+use columns::{ColumnConfig, Overflow};
+use modal::Modal;
+use config::{AppConfig, Density, IdleMode};
+use data_types::{parse_rank, ColumnType, TableData, DataSource, SortDir, DELTA_HEADER};
+use playlist::{Playlist, PlaylistEntry};
+use value::truncate;
+use ui::{mix, Styles, DARK_THEME, LIGHT_THEME};
 
 const VERSION: &str = "2.0.0-pre1";
-const UPDATE_INTERVAL: Duration = Duration::from_secs(5);
+const MIN_FONT_SCALE: f32 = 0.5;
+const MAX_FONT_SCALE: f32 = 3.0;
+const FONT_SCALE_STEP: f32 = 0.1;
+const MAX_COLUMN_WIDTH: f32 = 300.0;
+const VIRTUAL_OVERSCAN: usize = 5;
+const HIGHLIGHT_DURATION: Duration = Duration::from_secs(3);
+const HIGHLIGHT_TICK: Duration = Duration::from_millis(100);
+const AUTO_SCROLL_TICK: Duration = Duration::from_millis(50);
+const AUTO_SCROLL_PAUSE: Duration = Duration::from_secs(3);
+const MANUAL_SCROLL_PAUSE: Duration = Duration::from_secs(10);
+
+// Cloud polling slows down after this many fetches in a row return the same data,
+// doubling up to MAX_POLL_SLOWDOWN times the chosen interval
+const UNCHANGED_POLLS_BEFORE_SLOWDOWN: u32 = 5;
+const MAX_POLL_SLOWDOWN: u32 = 8;
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+// Typing pause before the cloud dialog lists a spreadsheet's sheets
+const PREFETCH_DELAY: Duration = Duration::from_millis(500);
+
+// Burn-in protection for an idle display
+const IDLE_DIM: f32 = 0.6;
+const IDLE_SHIFT_MAX: u16 = 4;
+const IDLE_SHIFTS: [(u16, u16); 4] = [(0, 0), (IDLE_SHIFT_MAX, 0), (IDLE_SHIFT_MAX, IDLE_SHIFT_MAX), (0, IDLE_SHIFT_MAX)];
+
+// Refresh intervals offered in the footer
+const REFRESH_PRESETS: [RefreshPreset; 4] = [
+    RefreshPreset(Duration::from_secs(1)),
+    RefreshPreset(Duration::from_secs(5)),
+    RefreshPreset(Duration::from_secs(15)),
+    RefreshPreset(Duration::from_secs(30)),
+];
+
+// Row limits offered in the footer
+const ROW_LIMITS: [RowLimit; 5] = [
+    RowLimit(None),
+    RowLimit(Some(10)),
+    RowLimit(Some(20)),
+    RowLimit(Some(30)),
+    RowLimit(Some(50)),
+];
 
 // Static application state
 static THEME: Lazy<Arc<Mutex<Styles>>> = Lazy::new(|| {
     Arc::new(Mutex::new(DARK_THEME.clone()))
 });
 
+static TABLE_SCROLL: Lazy<scrollable::Id> = Lazy::new(scrollable::Id::unique);
+static FROZEN_SCROLL: Lazy<scrollable::Id> = Lazy::new(scrollable::Id::unique);
+
 pub fn main() -> iced::Result {
+    logging::init();
+    let config = AppConfig::load();
+    
+    // Headless export for scripts, otherwise open the viewer
+    let window_args = match cli::parse(std::env::args().skip(1)) {
+        Ok(cli::Command::Gui(window)) => window,
+        Ok(cli::Command::Help) => {
+            println!("{}", cli::USAGE);
+            std::process::exit(cli::EXIT_OK);
+        }
+        Ok(cli::Command::Export(args)) => std::process::exit(cli::run_export(args, &config)),
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, cli::USAGE);
+            std::process::exit(cli::EXIT_USAGE);
+        }
+    };
+    
     ScoreViewer::run(Settings {
         window: window::Settings {
-            size: (1024, 768),
+            size: window_args.size(&config),
+            position: match window_args.position(&config) {
+                Some((x, y)) => window::Position::Specific(x, y),
+                None => window::Position::Centered,
+            },
             resizable: true,
-            decorations: false, // For fullscreen-like appearance
+            decorations: config.decorations, // Off by default for fullscreen-like appearance
             ..Default::default()
         },
+        default_font: fonts::default_font(&config),
+        flags: (config, window_args),
+        // Closing the window goes through Message::Exit so settings get saved
+        exit_on_close_request: false,
         ..Settings::default()
     })
 }
@@ -47,31 +143,169 @@ struct ScoreViewer {
     theme: Arc<Mutex<Styles>>,
     is_dark_mode: bool,
     data_source: Option<DataSource>,
-    file_path: Option<PathBuf>,
     spreadsheet_url: String,
-    sheet_name: String,
+    sheet_names: Vec<String>,
+    selected_tab: usize,
+    tab_data: Vec<Option<TableData>>,
     last_data: Option<TableData>,
     last_check: Instant,
-    last_modified: Option<std::time::SystemTime>,
+    local_states: Vec<FileState>,  // One per watched local file
+    pending_local: Option<Vec<FileStamp>>,  // Files seen changing, read once they hold still
+    load_id: u64,  // Bumped whenever the data source changes
+    row_changes: HashMap<String, (RowChange, Instant)>,  // Keyed by TableData::row_key
+    ui_font: Font,
+    scheduled_dark: Option<bool>,  // Last mode applied by the auto theme schedule
     cloud_dialog_open: bool,
     cloud_url_input: String,
     cloud_sheet_input: String,
+    cloud_csv_input: String,
+    cloud_dialog_error: Option<String>,
+    cloud_test_result: Option<Result<String, String>>,
+    available_sheets: Vec<String>,  // Worksheet titles listed from the spreadsheet
+    sheets_id: Option<String>,      // Spreadsheet ID the typed URL last resolved to
+    cloud: Arc<dyn CloudClient>,    // The Sheets API, or canned data with --demo
+    url_validators: Validators,
     result_column_index: Option<usize>,
-    scroll_state: scrollable::State,
+    rank_column_index: Option<usize>,
+    error_message: Option<String>,
+    column_config: ColumnConfig,
+    config: AppConfig,
+    refresh_interval: Duration,
+    sort: Option<(usize, SortDir)>,
+    filter: String,
+    facets: BTreeMap<String, BTreeSet<String>>,  // Column name to the values shown, combined with AND
+    facet_open: Option<usize>,  // Column whose value list is open
+    last_updated: Option<DateTime<Local>>,
+    is_stale: bool,
+    help_open: bool,
+    settings_open: bool,
+    info_open: bool,
+    last_error: Option<(DateTime<Local>, String)>,  // Kept after recovery, for the info overlay
+    is_loading: bool,
+    font_scale: f32,
+    auto_scroll: bool,
+    scroll_offset: f32,
+    scroll_paused_until: Option<Instant>,
+    column_widths: Vec<u16>,
+    paginate: bool,
+    page: usize,
+    viewport_height: f32,  // Of the table body, from its scrollable; the window height until scrolled
+    window_width: f32,
+    window_height: f32,
+    playlist: Playlist,
+    playlist_title: Option<String>,  // Banner title of the playlist entry on screen
+    selected_row: Option<usize>,  // Index into last_data's rows, kept on the same competitor across refreshes
+    poll_slowdown: u32,           // Multiplier on the refresh interval for cloud sources
+    unchanged_polls: u32,
+    poll_paused_until: Option<Instant>,  // Set from Retry-After when the quota runs out
+    last_activity: Instant,  // Last data change or operator input
+    snapshot: Option<(DateTime<Local>, TableData)>,  // Earlier table the rank changes are shown against
+    shown: Option<TableData>,  // last_data as drawn, see shown_data
+    updates_paused: bool,  // Standings held as they are, e.g. during the award ceremony
+    idle_since: Option<Instant>,  // Burn-in protection is on
+    fullscreen: bool,  // Window mode of this launch; --fullscreen and --maximized aren't saved
+    maximized: bool,
+    remember_window: bool,  // Off when --size or --position placed the window
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RefreshPreset(Duration);
+
+impl fmt::Display for RefreshPreset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Every {}s", self.0.as_secs())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RowLimit(Option<usize>);
+
+impl fmt::Display for RowLimit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(rows) => write!(f, "{} {}", labels::TOP_ROWS, rows),
+            None => write!(f, "{}", labels::ALL_ROWS),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 enum Message {
     ToggleTheme,
     OpenLocalFile,
-    FileSelected(Option<PathBuf>),
+    FilesSelected(Option<Vec<PathBuf>>),
     ShowCloudDialog,
     CloseCloudDialog,
     ConnectToCloud,
     UpdateCloudUrl(String),
     UpdateSheetName(String),
-    DataUpdated(TableData),
+    UpdateCsvUrl(String),
+    TestConnection,
+    ListSheets,
+    SheetsListed(Result<Vec<String>, String>),
+    PrefetchSheets(String),  // The URL as typed when the delay started
+    SheetsPrefetched(String, Option<Vec<String>>),  // Background listing for the URL it was started for
+    PickSheet(String),
+    ConnectionTested(Result<String, String>),
+    // Load results carry the id of the source they were started for
+    UrlFetched(u64, UrlFetch),
+    DataUpdated(u64, TableData),
+    LoadFailed(u64, String),
+    CloudError(u64, String),
+    RateLimited(u64, Option<Duration>),
+    SheetUpdated(u64, usize, TableData),
+    SelectTab(usize),
     CheckForUpdates,
+    SetRefreshInterval(Duration),
+    SetRowLimit(Option<usize>),
+    SortBy(usize),
+    UpdateFilter(String),
+    ClearFilter,
+    OpenFacet(usize),
+    CloseFacet,
+    ToggleFacetValue(String, String),
+    ClearFacet(String),
+    ToggleFullscreen,
+    ToggleDecorations,
+    ToggleHelp,
+    ToggleInfo,
+    ToggleSettings,
+    ToggleStripes,
+    ToggleMedals,
+    ToggleChangeHighlights,
+    SetFontScale(f32),
+    EscapePressed,
+    ForceRefresh,
+    Reconnect,
+    PlaylistTick,
+    UserActivity,
+    TogglePlaylistPause,
+    SkipSource,
+    ExportImage,
+    ZoomIn,
+    ZoomOut,
+    ToggleAutoScroll,
+    TogglePagination,
+    ToggleDensity,
+    ToggleFitWidth,
+    ToggleSummary,
+    ToggleSnapshot,
+    ToggleUpdatesPaused,
+    ToggleClock,
+    TogglePin(String),
+    SelectRow(usize),
+    NextPage,
+    PrevPage,
+    AutoScrollTick,
+    HighlightTick,
+    Scrolled(scrollable::Viewport),
+    FrozenScrolled(scrollable::Viewport),
+    WindowResized(u32, u32),
+    WindowMoved(i32, i32),
+    LoadTheme,
+    ThemeLoaded(Option<PathBuf>),
+    ExportFinished(Result<(), String>),
+    FontLoaded,
     Exit,
 }
 
@@ -79,28 +313,117 @@ impl Application for ScoreViewer {
     type Executor = executor::Default;
     type Message = Message;
     type Theme = Theme;
-    type Flags = ();
-
-    fn new(_flags: ()) -> (Self, Command<Message>) {
-        (
-            ScoreViewer {
-                theme: THEME.clone(),
-                is_dark_mode: true,
-                data_source: None,
-                file_path: None,
-                spreadsheet_url: String::new(),
-                sheet_name: String::new(),
-                last_data: None,
-                last_check: Instant::now(),
-                last_modified: None,
-                cloud_dialog_open: false,
-                cloud_url_input: String::new(),
-                cloud_sheet_input: String::new(),
-                result_column_index: None,
-                scroll_state: scrollable::State::new(),
+    type Flags = (AppConfig, cli::WindowArgs);
+
+    fn new((mut config, window_args): (AppConfig, cli::WindowArgs)) -> (Self, Command<Message>) {
+        config.demo = window_args.demo;
+        let column_config = ColumnConfig::load();
+        
+        let mut viewer = ScoreViewer {
+            theme: THEME.clone(),
+            is_dark_mode: true,
+            data_source: None,
+            spreadsheet_url: String::new(),
+            sheet_names: Vec::new(),
+            selected_tab: 0,
+            tab_data: Vec::new(),
+            last_data: None,
+            last_check: Instant::now(),
+            local_states: Vec::new(),
+            pending_local: None,
+            load_id: 0,
+            row_changes: HashMap::new(),
+            ui_font: fonts::default_font(&config),
+            scheduled_dark: None,
+            cloud_dialog_open: false,
+            cloud_url_input: String::new(),
+            cloud_sheet_input: String::new(),
+            cloud_csv_input: String::new(),
+            cloud_dialog_error: None,
+            cloud_test_result: None,
+            available_sheets: Vec::new(),
+            sheets_id: None,
+            cloud: cloud_client(&config, column_config.clone()),
+            url_validators: Validators::default(),
+            result_column_index: None,
+            rank_column_index: None,
+            error_message: None,
+            column_config,
+            refresh_interval: config.refresh_interval(),
+            sort: config.sort,
+            filter: config.filter.clone(),
+            facets: config.facets.clone(),
+            facet_open: None,
+            last_updated: None,
+            is_stale: false,
+            help_open: false,
+            settings_open: false,
+            info_open: false,
+            last_error: None,
+            is_loading: false,
+            font_scale: config.font_scale.clamp(MIN_FONT_SCALE, MAX_FONT_SCALE),
+            auto_scroll: false,
+            scroll_offset: 0.0,
+            scroll_paused_until: None,
+            column_widths: Vec::new(),
+            paginate: false,
+            page: 0,
+            viewport_height: window_args.size(&config).1 as f32,
+            window_width: window_args.size(&config).0 as f32,
+            window_height: window_args.size(&config).1 as f32,
+            playlist: Playlist::new(config.playlist.clone()),
+            playlist_title: None,
+            selected_row: None,
+            poll_slowdown: 1,
+            unchanged_polls: 0,
+            poll_paused_until: None,
+            last_activity: Instant::now(),
+            snapshot: None,
+            shown: None,
+            updates_paused: false,
+            idle_since: None,
+            fullscreen: config.fullscreen || window_args.fullscreen,
+            maximized: config.maximized || window_args.maximized,
+            remember_window: !window_args.places_window(),
+            config,
+        };
+        
+        // Custom theme from the last session, if it still loads
+        if let Some(ref path) = viewer.config.theme_path {
+            if let Ok(styles) = Styles::from_file(path) {
+                *viewer.theme.lock().unwrap() = styles;
+            }
+        }
+        
+        viewer.apply_theme_schedule();
+        
+        // Start the demo or the playlist if there is one, else reopen the last used data source
+        let load = match viewer.playlist.current().cloned() {
+            _ if viewer.config.demo => {
+                viewer.open_source(DataSource::Cloud(DEMO_URL.to_string(), MockCloudHandler::sheet_names()))
+            }
+            Some(entry) => viewer.show_playlist_entry(entry),
+            None => match viewer.config.data_source.clone() {
+                Some(source) if source.local_files().map_or(false, |paths| paths.iter().any(|path| !path.exists())) => {
+                    Command::none()
+                }
+                Some(source) => viewer.open_source(source),
+                None => Command::none(),
             },
-            Command::none(),
-        )
+        };
+        
+        let mode = if viewer.fullscreen {
+            window::change_mode(window::Mode::Fullscreen)
+        } else if viewer.maximized {
+            window::maximize(true)
+        } else {
+            Command::none()
+        };
+        
+        // The window starts with the default font; load its glyphs straight away
+        let font = font::load(fonts::font_bytes(&viewer.config)).map(|_| Message::FontLoaded);
+        
+        (viewer, Command::batch(vec![font, load, mode]))
     }
 
     fn title(&self) -> String {
@@ -110,57 +433,74 @@ impl Application for ScoreViewer {
     fn update(&mut self, message: Message) -> Command<Message> {
         match message {
             Message::ToggleTheme => {
-                self.is_dark_mode = !self.is_dark_mode;
-                let mut theme = self.theme.lock().unwrap();
-                *theme = if self.is_dark_mode {
-                    DARK_THEME.clone()
-                } else {
-                    LIGHT_THEME.clone()
-                };
+                // Back to the built-in themes; in auto mode this lasts until the next scheduled switch
+                self.config.theme_path = None;
+                self.config.save();
+                self.set_dark_mode(!self.is_dark_mode);
                 Command::none()
             }
             
             Message::OpenLocalFile => {
                 Command::perform(
                     async {
-                        let file = FileDialog::new()
-                            .add_filter("CSV Files", &["csv"])
-                            .pick_file();
-                        file
+                        // Several CSVs with the same columns are shown as one table
+                        let files = FileDialog::new()
+                            .add_filter("Result Files", &["csv", "gz", "xlsx"])
+                            .add_filter("CSV Files", &["csv", "gz"])
+                            .add_filter("Excel Files", &["xlsx"])
+                            .pick_files();
+                        files
                     },
-                    Message::FileSelected,
+                    Message::FilesSelected,
                 )
             }
             
-            Message::FileSelected(path_opt) => {
-                if let Some(path) = path_opt {
-                    self.file_path = Some(path.clone());
-                    self.data_source = Some(DataSource::Local(path.clone()));
-                    self.last_modified = fs::metadata(&path).ok().map(|m| m.modified().unwrap_or_else(|_| std::time::SystemTime::now()));
-                    
-                    return Command::perform(
-                        async move {
-                            let csv_handler = CSVHandler::new();
-                            csv_handler.read_csv(&path).await
-                        },
-                        Message::DataUpdated
-                    );
+            Message::FilesSelected(paths) => {
+                if let Some(paths) = paths.filter(|paths| !paths.is_empty()) {
+                    self.hold_playlist();
+                    self.select_local(paths.clone());
+                    return self.load_local(paths);
                 }
                 Command::none()
             }
             
             Message::ShowCloudDialog => {
                 self.cloud_dialog_open = true;
+                if self.available_sheets.is_empty() {
+                    return self.prefetch_sheets();
+                }
                 Command::none()
             }
             
             Message::CloseCloudDialog => {
-                self.cloud_dialog_open = false;
+                self.close_cloud_dialog();
                 Command::none()
             }
             
             Message::UpdateCloudUrl(url) => {
                 self.cloud_url_input = url;
+                self.cloud_dialog_error = None;
+                
+                // Only a different spreadsheet needs its sheets listed again
+                let id = CloudHandler::extract_spreadsheet_id(&self.cloud_url_input).ok().map(str::to_string);
+                if id == self.sheets_id {
+                    return Command::none();
+                }
+                self.sheets_id = id;
+                self.available_sheets.clear();
+                if self.sheets_id.is_none() {
+                    return Command::none();
+                }
+                
+                // Wait for typing to pause, as every keystroke of a bare ID is a new ID
+                let url = self.cloud_url_input.trim().to_string();
+                Command::perform(tokio::time::sleep(PREFETCH_DELAY), move |_| Message::PrefetchSheets(url))
+            }
+            
+            Message::PrefetchSheets(url) => {
+                if self.is_current_spreadsheet(&url) {
+                    return self.prefetch_sheets();
+                }
                 Command::none()
             }
             
@@ -169,84 +509,340 @@ impl Application for ScoreViewer {
                 Command::none()
             }
             
-            Message::ConnectToCloud => {
-                if !self.cloud_url_input.is_empty() {
-                    self.spreadsheet_url = self.cloud_url_input.clone();
-                    self.sheet_name = self.cloud_sheet_input.clone();
-                    self.data_source = Some(DataSource::Cloud(
-                        self.spreadsheet_url.clone(), 
-                        self.sheet_name.clone()
-                    ));
-                    self.cloud_dialog_open = false;
-                    
-                    let url = self.spreadsheet_url.clone();
-                    let sheet = self.sheet_name.clone();
-                    
+            Message::UpdateCsvUrl(url) => {
+                self.cloud_csv_input = url;
+                Command::none()
+            }
+            
+            Message::TestConnection => {
+                // Same fetch as Connect, but the result only goes to the dialog
+                // and nothing is written to the cache
+                let columns = self.column_config.clone();
+                let timeout = self.config.request_timeout();
+                
+                if self.cloud_url_input.is_empty() && !self.cloud_csv_input.trim().is_empty() {
+                    let url = self.cloud_csv_input.trim().to_string();
+                    self.cloud_test_result = Some(Ok("Testing...".to_string()));
                     return Command::perform(
                         async move {
-                            let cloud_handler = CloudHandler::new();
-                            cloud_handler.fetch_data(&url, &sheet).await
-                                .unwrap_or_else(|_| TableData::empty())
+                            match UrlHandler::new(columns).with_timeout(timeout).fetch(&url, &Validators::default()).await {
+                                Ok(UrlFetch::Updated(data, _)) => Ok(data),
+                                Ok(UrlFetch::NotModified) => Err("server returned no data".to_string()),
+                                Err(err) => Err(err.to_string()),
+                            }
                         },
-                        Message::DataUpdated
+                        |result| Message::ConnectionTested(result.map(|data| describe_table(&data))),
                     );
                 }
+                
+                if let Err(err) = CloudHandler::extract_spreadsheet_id(&self.cloud_url_input) {
+                    self.cloud_dialog_error = Some(err.to_string());
+                    return Command::none();
+                }
+                
+                let url = self.cloud_url_input.trim().to_string();
+                let sheet = self.cloud_sheet_input
+                    .split(',')
+                    .map(|sheet| sheet.trim().to_string())
+                    .find(|sheet| !sheet.is_empty())
+                    .unwrap_or_default();
+                let cloud = self.cloud.clone();
+                self.cloud_test_result = Some(Ok("Testing...".to_string()));
+                
+                Command::perform(
+                    async move { cloud.fetch_uncached(&url, &sheet).await.map_err(|err| err.to_string()) },
+                    |result| Message::ConnectionTested(result.map(|data| describe_table(&data))),
+                )
+            }
+            
+            Message::ListSheets => {
+                if let Err(err) = CloudHandler::extract_spreadsheet_id(&self.cloud_url_input) {
+                    self.cloud_dialog_error = Some(err.to_string());
+                    return Command::none();
+                }
+                
+                let url = self.cloud_url_input.trim().to_string();
+                let cloud = self.cloud.clone();
+                
+                Command::perform(
+                    async move { cloud.list_sheets(&url).await.map_err(|err| err.to_string()) },
+                    Message::SheetsListed,
+                )
+            }
+            
+            Message::SheetsListed(result) => {
+                match result {
+                    Ok(sheets) => {
+                        self.available_sheets = sheets;
+                        self.cloud_dialog_error = None;
+                    }
+                    Err(err) => {
+                        // The typed sheet name still works, so point the operator there
+                        self.available_sheets.clear();
+                        self.cloud_dialog_error = Some(format!(
+                            "Couldn't list the sheets ({}). Type the sheet name below instead.", err
+                        ));
+                    }
+                }
+                Command::none()
+            }
+            
+            Message::SheetsPrefetched(url, sheets) => {
+                // Without metadata the sheet name just goes unchecked
+                if self.is_current_spreadsheet(&url) {
+                    if let Some(sheets) = sheets {
+                        self.available_sheets = sheets;
+                    }
+                }
+                Command::none()
+            }
+            
+            Message::PickSheet(sheet) => {
+                self.cloud_sheet_input = sheet;
+                Command::none()
+            }
+            
+            Message::ConnectionTested(result) => {
+                self.cloud_test_result = Some(result);
+                Command::none()
+            }
+            
+            Message::ConnectToCloud => {
+                // A plain CSV URL is only used when no Google Sheet is given
+                if self.cloud_url_input.is_empty() && !self.cloud_csv_input.trim().is_empty() {
+                    let url = self.cloud_csv_input.trim().to_string();
+                    self.hold_playlist();
+                    self.select_url(url.clone());
+                    self.close_cloud_dialog();
+                    return self.load_url(url);
+                }
+                
+                if !self.cloud_url_input.is_empty() {
+                    // Keep the dialog open so the URL can be corrected
+                    if let Err(err) = CloudHandler::extract_spreadsheet_id(&self.cloud_url_input) {
+                        self.cloud_dialog_error = Some(err.to_string());
+                        return Command::none();
+                    }
+                    
+                    let url = self.cloud_url_input.trim().to_string();
+                    
+                    // Several tabs can be given as a comma-separated list
+                    let mut sheets: Vec<String> = self.cloud_sheet_input
+                        .split(',')
+                        .map(|sheet| sheet.trim().to_string())
+                        .filter(|sheet| !sheet.is_empty())
+                        .collect();
+                    if sheets.is_empty() {
+                        sheets.push(String::new());
+                    }
+                    
+                    self.hold_playlist();
+                    self.select_cloud(url, sheets);
+                    self.close_cloud_dialog();
+                    
+                    return self.load_all_tabs();
+                }
+                Command::none()
+            }
+            
+            Message::DataUpdated(id, _) | Message::UrlFetched(id, _) | Message::SheetUpdated(id, _, _)
+            | Message::LoadFailed(id, _) | Message::CloudError(id, _)
+            | Message::RateLimited(id, _) if id != self.load_id => {
+                // A slow load for a source that has since been replaced
+                Command::none()
+            }
+            
+            Message::DataUpdated(_, data) => {
+                log::debug!("Showing {} rows, {} columns", data.rows.len(), data.headers.len());
+                
+                // Keep the reader's place: the competitor at the top of the view, or
+                // failing that the distance in pixels from the top
+                let anchor = self.scroll_anchor();
+                let previous_offset = self.scroll_offset * self.scrollable_height();
+                
+                // Poll less often while nothing changes, and at the chosen rate once it does
+                let unchanged = self.last_data.as_ref() == Some(&data);
+                if unchanged {
+                    self.unchanged_polls += 1;
+                    if self.unchanged_polls >= UNCHANGED_POLLS_BEFORE_SLOWDOWN {
+                        self.poll_slowdown = (self.poll_slowdown * 2).min(MAX_POLL_SLOWDOWN);
+                        self.unchanged_polls = 0;
+                    }
+                } else {
+                    self.poll_slowdown = 1;
+                    self.unchanged_polls = 0;
+                }
+                self.poll_paused_until = None;
+                
+                // Identical data leaves the table, scroll position and highlights alone
+                if unchanged {
+                    self.is_loading = false;
+                    self.error_message = None;
+                    self.last_updated = Some(Local::now());
+                    self.is_stale = false;
+                    return Command::none();
+                }
+                
+                // Flash the rows that differ from the previous refresh of the same table
+                if let Some(old) = self.last_data.as_ref().filter(|_| self.config.change_highlights) {
+                    if old.headers == data.headers {
+                        // Hidden cells changing alone don't flash the row
+                        let now = Instant::now();
+                        for (key, change) in diff_rows(&old.visible_view(), &data.visible_view()) {
+                            self.row_changes.insert(key, (change, now));
+                        }
+                    }
+                }
+                
+                self.set_data(data);
+                self.note_activity();
+                self.is_loading = false;
+                self.error_message = None;
+                self.last_updated = Some(Local::now());
+                self.is_stale = false;
+                
+                // Remember the source so it can be reopened on the next launch
+                if !self.config.demo && self.config.data_source != self.data_source {
+                    self.config.data_source = self.data_source.clone();
+                    self.config.save();
+                }
+                
+                // Refreshes rebuild the table, so put the scroll position back,
+                // clamped to the end if the table got shorter
+                self.scroll_offset = anchor.and_then(|anchor| self.anchored_offset(&anchor))
+                    .unwrap_or_else(|| (previous_offset / self.scrollable_height()).clamp(0.0, 1.0));
+                self.snap_scroll()
+            }
+            
+            Message::UrlFetched(id, fetch) => {
+                match fetch {
+                    UrlFetch::Updated(data, validators) => {
+                        self.url_validators = validators;
+                        self.update(Message::DataUpdated(id, data))
+                    }
+                    UrlFetch::NotModified => {
+                        log::debug!("Source not modified since the last fetch");
+                        // Same data as before, so it's as fresh as it can be
+                        self.is_loading = false;
+                        self.error_message = None;
+                        self.last_updated = Some(Local::now());
+                        self.is_stale = false;
+                        Command::none()
+                    }
+                }
+            }
+            
+            Message::SheetUpdated(id, tab, data) => {
+                if let Some(slot) = self.tab_data.get_mut(tab) {
+                    *slot = Some(data.clone());
+                }
+                if tab == self.selected_tab {
+                    return self.update(Message::DataUpdated(id, data));
+                }
+                Command::none()
+            }
+            
+            Message::SelectTab(tab) => {
+                if tab < self.sheet_names.len() && tab != self.selected_tab {
+                    self.selected_tab = tab;
+                    self.sort = None;
+                    self.snapshot = None;
+                    self.update_shown();
+                    self.save_view_state();
+                    
+                    // Show what we already have for the tab while it refreshes
+                    if let Some(Some(data)) = self.tab_data.get(tab) {
+                        self.set_data(data.clone());
+                    }
+                    return self.load_cloud(self.spreadsheet_url.clone(), tab);
+                }
+                Command::none()
+            }
+            
+            Message::LoadFailed(_, error) => {
+                log::error!("Load failed: {}", error);
+                
+                // Keep whatever was loaded before and just report the failure
+                self.last_error = Some((Local::now(), error.clone()));
+                self.error_message = Some(error);
+                self.is_loading = false;
                 Command::none()
             }
             
-            Message::DataUpdated(data) => {
-                self.last_data = Some(data);
-                // Find result column index
-                if let Some(ref data) = self.last_data {
-                    if !data.headers.is_empty() {
-                        self.result_column_index = data.headers
-                            .iter()
-                            .position(|h| h.to_lowercase() == "result");
+            Message::CloudError(_, error) => {
+                log::error!("Google Sheets fetch failed: {}", error);
+                
+                // Leave the last good table on screen so the display never blanks
+                self.error_message = Some(format!("Google Sheets fetch failed: {}", error));
+                self.last_error = Some((Local::now(), error));
+                self.is_loading = false;
+                
+                // Nothing on screen yet, e.g. after a restart during an outage
+                if self.last_data.is_none() {
+                    if let Some(DataSource::Cloud(url, _)) = &self.data_source {
+                        if let Some((data, fetched_at)) = CloudHandler::cached_data(url, &self.selected_sheet()) {
+                            log::warn!("Showing cached data from {}", fetched_at);
+                            self.set_data(data);
+                            self.last_updated = Some(fetched_at);
+                        }
                     }
                 }
+                
+                self.is_stale = self.last_data.is_some();
                 Command::none()
             }
             
+            Message::RateLimited(id, retry_after) => {
+                // Wait as long as Google asks, and poll more slowly afterwards
+                let wait = retry_after.unwrap_or(DEFAULT_RETRY_AFTER);
+                log::warn!("Google Sheets quota exceeded, pausing polling for {}s", wait.as_secs());
+                self.poll_paused_until = Some(Instant::now() + wait);
+                self.poll_slowdown = (self.poll_slowdown * 2).min(MAX_POLL_SLOWDOWN);
+                self.update(Message::CloudError(id, format!("read quota exceeded, retrying in {}s", wait.as_secs())))
+            }
+            
             Message::CheckForUpdates => {
-                if Instant::now().duration_since(self.last_check) >= UPDATE_INTERVAL {
+                self.apply_theme_schedule();
+                self.check_idle();
+                
+                // Held standings stay put; the clock keeps ticking from this same timer
+                if self.updates_paused {
+                    return Command::none();
+                }
+                
+                // A file that was still changing is checked again on the very next tick
+                let settling = self.pending_local.is_some();
+                
+                if settling || Instant::now().duration_since(self.last_check) >= self.effective_interval() {
                     self.last_check = Instant::now();
                     
-                    match &self.data_source {
-                        Some(DataSource::Local(path)) => {
-                            if let Ok(metadata) = fs::metadata(path) {
-                                if let Ok(modified) = metadata.modified() {
-                                    if let Some(last_modified) = self.last_modified {
-                                        if modified > last_modified {
-                                            self.last_modified = Some(modified);
-                                            let path_clone = path.clone();
-                                            
-                                            return Command::perform(
-                                                async move {
-                                                    let csv_handler = CSVHandler::new();
-                                                    csv_handler.read_csv(&path_clone).await
-                                                },
-                                                Message::DataUpdated
-                                            );
-                                        }
-                                    } else {
-                                        self.last_modified = Some(modified);
-                                    }
+                    match self.data_source.clone() {
+                        Some(DataSource::Local(_)) | Some(DataSource::Files(_)) => {
+                            let paths = self.data_source.as_ref().and_then(DataSource::local_files).unwrap_or_default();
+                            if self.local_files_changed(&paths) {
+                                // Only read once the files look the same on two checks in a row,
+                                // so a half-written export is never shown
+                                let stamps: Vec<FileStamp> = paths.iter().map(|path| file_stamp(path)).collect();
+                                if settled(&mut self.pending_local, stamps) {
+                                    self.record_local_state(&paths);
+                                    return self.load_local(paths);
                                 }
+                            } else {
+                                self.pending_local = None;
                             }
                         }
                         
-                        Some(DataSource::Cloud(url, sheet)) => {
-                            let url_clone = url.clone();
-                            let sheet_clone = sheet.clone();
+                        Some(DataSource::Cloud(url, _)) => {
+                            if self.poll_paused_until.map_or(false, |until| Instant::now() < until) {
+                                return Command::none();
+                            }
                             
-                            return Command::perform(
-                                async move {
-                                    let cloud_handler = CloudHandler::new();
-                                    cloud_handler.fetch_data(&url_clone, &sheet_clone).await
-                                        .unwrap_or_else(|_| TableData::empty())
-                                },
-                                Message::DataUpdated
-                            );
+                            // Only the visible tab is kept live
+                            return self.load_cloud(url, self.selected_tab);
+                        }
+                        
+                        Some(DataSource::Url(url)) => {
+                            return self.load_url(url);
                         }
                         
                         None => {}
@@ -255,173 +851,2090 @@ impl Application for ScoreViewer {
                 Command::none()
             }
             
-            Message::Exit => {
-                // Exit the application
-                std::process::exit(0);
+            Message::SetRowLimit(limit) => {
+                self.config.max_rows = limit;
+                self.config.save();
+                Command::none()
             }
-        }
-    }
-
-    fn subscription(&self) -> Subscription<Message> {
-        // Create a subscription that emits a CheckForUpdates message every second
-        iced::time::every(Duration::from_secs(1))
-            .map(|_| Message::CheckForUpdates)
-    }
-
-    fn view(&self) -> Element<Message> {
-        let theme = self.theme.lock().unwrap();
-        
-        // Main content area with table
-        let content = if let Some(ref data) = self.last_data {
-            self.render_table(data, &theme)
-        } else {
-            container(
-                text("No data loaded. Please select a local file or connect to Google Sheets.")
-                    .size(24)
-                    .color(theme.fg)
-                    .horizontal_alignment(Horizontal::Center)
-            )
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .center_x()
-            .center_y()
-            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
-                bg: theme.bg,
-            })))
-            .into()
-        };
-        
-        // Footer with buttons
-        let footer = row![
-            text(format!("Score Viewer © 2024-2025 Joona Holkko. All rights reserved. v{}", VERSION))
-                .size(14)
-                .color(theme.footer_fg),
-            Space::with_width(Length::Fill),
-            button(
-                text("☁ Cloud")
-                    .horizontal_alignment(Horizontal::Center)
-                    .size(16)
-                    .color(theme.footer_fg)
-            )
-            .on_press(Message::ShowCloudDialog)
-            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
-                bg: theme.footer_bg,
-                fg: theme.footer_fg,
-                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
-            }))),
-            Space::with_width(Length::Units(10)),
-            button(
-                text("📁 Local")
-                    .horizontal_alignment(Horizontal::Center)
-                    .size(16)
-                    .color(theme.footer_fg)
-            )
-            .on_press(Message::OpenLocalFile)
-            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
-                bg: theme.footer_bg,
-                fg: theme.footer_fg,
-                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
-            }))),
-            Space::with_width(Length::Units(10)),
-            button(
-                text("💡")
-                    .horizontal_alignment(Horizontal::Center)
-                    .size(18)
-                    .color(theme.footer_fg)
-            )
-            .on_press(Message::ToggleTheme)
-            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
-                bg: theme.footer_bg,
-                fg: theme.footer_fg,
-                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
-            })))
-        ]
-        .spacing(5)
-        .padding(10)
-        .width(Length::Fill)
-        .height(Length::Units(50))
-        .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
-            bg: theme.footer_bg,
-        })));
-        
-        // Combine the main content and footer
-        let main_content = column![
-            content,
-            footer
-        ];
-        
-        // Overlay for cloud connection dialog
-        if self.cloud_dialog_open {
-            return self.cloud_dialog_view(&theme);
-        }
-        
-        container(main_content)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
-                bg: theme.bg,
-            })))
-            .into()
-    }
-}
-
-impl ScoreViewer {
-    fn render_table(&self, data: &TableData, theme: &Styles) -> Element<Message> {
-        let headers = Row::with_children(
-            data.headers.iter().enumerate().map(|(i, header)| {
-                container(
-                    text(header)
-                        .size(18)
-                        .color(theme.header_fg)
-                )
-                .width(if i == 0 { Length::Units(150) } else { Length::Units(100) })
-                .padding(5)
-                .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
-                    bg: theme.header_bg,
-                })))
-                .into()
-            })
-            .collect()
-        )
-        .spacing(1);
-        
-        let rows = data.rows.iter().map(|row| {
-            Row::with_children(
-                row.iter().enumerate().map(|(i, cell)| {
-                    let is_result_column = self.result_column_index.map_or(false, |idx| idx == i);
-                    
-                    container(
-                        text(cell)
-                            .size(18)
-                            .color(theme.fg)
-                            .style(if is_result_column {
-                                iced::theme::Text::Default
-                            } else {
-                                iced::theme::Text::Default
-                            })
-                    )
-                    .width(if i == 0 { Length::Units(150) } else { Length::Units(100) })
-                    .padding(5)
-                    .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
-                        bg: theme.bg,
-                    })))
-                    .into()
-                })
-                .collect()
-            )
-            .spacing(1)
-        });
-        
-        let content = column![headers]
-            .push(Column::with_children(rows.collect()))
-            .spacing(1);
-        
-        scrollable(content)
-            .height(Length::Fill)
-            .into()
-    }
-    
+            
+            Message::SetRefreshInterval(interval) => {
+                self.refresh_interval = interval;
+                self.config.refresh_interval_secs = interval.as_secs();
+                self.config.save();
+                Command::none()
+            }
+            
+            Message::SortBy(column) => {
+                // Clicking the sorted column again flips the direction
+                self.sort = match self.sort {
+                    Some((current, dir)) if current == column => Some((column, dir.toggled())),
+                    _ => Some((column, SortDir::Ascending)),
+                };
+                self.save_view_state();
+                Command::none()
+            }
+            
+            Message::UpdateFilter(filter) => {
+                self.filter = filter;
+                self.page = 0;
+                self.save_view_state();
+                Command::none()
+            }
+            
+            Message::ClearFilter => {
+                self.filter.clear();
+                self.page = 0;
+                self.save_view_state();
+                Command::none()
+            }
+            
+            Message::ToggleFullscreen => {
+                self.fullscreen = !self.fullscreen;
+                self.config.fullscreen = self.fullscreen;
+                self.config.save();
+                window::change_mode(if self.fullscreen {
+                    window::Mode::Fullscreen
+                } else {
+                    window::Mode::Windowed
+                })
+            }
+            
+            Message::ToggleDecorations => {
+                self.config.decorations = !self.config.decorations;
+                self.config.save();
+                window::toggle_decorations()
+            }
+            
+            Message::ToggleHelp => {
+                self.help_open = !self.help_open;
+                Command::none()
+            }
+            
+            Message::ToggleInfo => {
+                self.info_open = !self.info_open;
+                Command::none()
+            }
+            
+            Message::ToggleSettings => {
+                if self.settings_open {
+                    self.close_settings();
+                } else {
+                    self.settings_open = true;
+                }
+                Command::none()
+            }
+            
+            // Settings apply at once and are saved when the overlay closes
+            Message::ToggleStripes => {
+                self.config.striped_rows = !self.config.striped_rows;
+                Command::none()
+            }
+            
+            Message::ToggleMedals => {
+                self.config.medals = !self.config.medals;
+                Command::none()
+            }
+            
+            // Take a snapshot of the table to compare against, or drop the one taken
+            Message::ToggleSnapshot => {
+                self.snapshot = match self.snapshot {
+                    Some(_) => None,
+                    None => self.last_data.clone().map(|data| (Local::now(), data)),
+                };
+                let sort = self.sort;
+                self.update_shown();
+                if self.sort != sort {
+                    self.save_view_state();
+                }
+                self.compute_column_widths();
+                Command::none()
+            }
+            
+            Message::ToggleChangeHighlights => {
+                self.config.change_highlights = !self.config.change_highlights;
+                if !self.config.change_highlights {
+                    self.row_changes.clear();
+                }
+                Command::none()
+            }
+            
+            Message::SetFontScale(scale) => {
+                self.font_scale = scale.clamp(MIN_FONT_SCALE, MAX_FONT_SCALE);
+                self.config.font_scale = self.font_scale;
+                self.compute_column_widths();
+                Command::none()
+            }
+            
+            Message::OpenFacet(column) => {
+                self.facet_open = Some(column);
+                Command::none()
+            }
+            
+            Message::CloseFacet => {
+                self.facet_open = None;
+                Command::none()
+            }
+            
+            Message::ToggleFacetValue(column, value) => {
+                let values = self.facets.entry(column.clone()).or_default();
+                // Untick any spelling of the value, else tick it
+                let before = values.len();
+                values.retain(|ticked| ticked.to_lowercase() != value.to_lowercase());
+                if values.len() == before {
+                    values.insert(value);
+                }
+                
+                // No values ticked means the column isn't filtered
+                if values.is_empty() {
+                    self.facets.remove(&column);
+                }
+                self.page = 0;
+                self.save_view_state();
+                Command::none()
+            }
+            
+            Message::ClearFacet(column) => {
+                self.facets.remove(&column);
+                self.page = 0;
+                self.save_view_state();
+                Command::none()
+            }
+            
+            Message::EscapePressed => {
+                // Close the topmost overlay, otherwise clear the search
+                if self.cloud_dialog_open {
+                    self.close_cloud_dialog();
+                } else if self.settings_open {
+                    self.close_settings();
+                } else if self.facet_open.is_some() {
+                    self.facet_open = None;
+                } else if self.help_open {
+                    self.help_open = false;
+                } else if self.info_open {
+                    self.info_open = false;
+                } else {
+                    self.filter.clear();
+                    self.save_view_state();
+                }
+                Command::none()
+            }
+            
+            Message::ForceRefresh => {
+                // Reload right away, whatever the mtime says, and restart the interval
+                self.last_check = Instant::now();
+                
+                match self.data_source.clone() {
+                    Some(DataSource::Local(path)) => {
+                        self.record_local_state(&[path.clone()]);
+                        self.load_local(vec![path])
+                    }
+                    Some(DataSource::Files(paths)) => {
+                        self.record_local_state(&paths);
+                        self.load_local(paths)
+                    }
+                    Some(DataSource::Cloud(url, _)) => self.load_cloud(url, self.selected_tab),
+                    Some(DataSource::Url(url)) => {
+                        // Download in full even if the server would say it's unchanged
+                        self.url_validators = Validators::default();
+                        self.load_url(url)
+                    }
+                    None => Command::none(),
+                }
+            }
+            
+            Message::Reconnect => {
+                // Fetch every tab again rather than waiting for the next poll, with the
+                // same handler and credentials; the banner clears once data arrives
+                self.last_check = Instant::now();
+                match self.data_source.clone() {
+                    Some(DataSource::Cloud(_, _)) => self.load_all_tabs(),
+                    _ => self.update(Message::ForceRefresh),
+                }
+            }
+            
+            Message::ToggleUpdatesPaused => {
+                self.updates_paused = !self.updates_paused;
+                if self.updates_paused {
+                    // Anything already on its way would change the standings, so drop it
+                    log::info!("Updates paused");
+                    self.load_id += 1;
+                    self.is_loading = false;
+                    self.pending_local = None;
+                    return Command::none();
+                }
+                
+                // Catch up on whatever changed while paused
+                log::info!("Updates resumed");
+                self.update(Message::ForceRefresh)
+            }
+            
+            Message::UserActivity => {
+                self.note_activity();
+                Command::none()
+            }
+            
+            Message::PlaylistTick => {
+                if self.playlist.is_due() && !self.updates_paused {
+                    return self.update(Message::SkipSource);
+                }
+                Command::none()
+            }
+            
+            Message::TogglePlaylistPause => {
+                self.playlist.toggle_paused();
+                Command::none()
+            }
+            
+            Message::SkipSource => {
+                match self.playlist.advance().cloned() {
+                    Some(entry) => self.show_playlist_entry(entry),
+                    None => Command::none(),
+                }
+            }
+            
+            Message::LoadTheme => {
+                Command::perform(
+                    async {
+                        FileDialog::new()
+                            .add_filter("Theme Files", &["toml", "json"])
+                            .pick_file()
+                    },
+                    Message::ThemeLoaded,
+                )
+            }
+            
+            Message::ThemeLoaded(path_opt) => {
+                if let Some(path) = path_opt {
+                    match Styles::from_file(&path) {
+                        Ok(styles) => {
+                            *self.theme.lock().unwrap() = styles;
+                            self.config.theme_path = Some(path);
+                            self.config.save();
+                        }
+                        Err(err) => {
+                            self.error_message = Some(format!("Could not load theme: {}", err));
+                        }
+                    }
+                }
+                Command::none()
+            }
+            
+            Message::ZoomIn | Message::ZoomOut => {
+                let step = if matches!(message, Message::ZoomIn) { FONT_SCALE_STEP } else { -FONT_SCALE_STEP };
+                self.font_scale = (self.font_scale + step).clamp(MIN_FONT_SCALE, MAX_FONT_SCALE);
+                self.config.font_scale = self.font_scale;
+                self.config.save();
+                self.compute_column_widths();
+                Command::none()
+            }
+            
+            Message::ToggleFitWidth => {
+                self.config.fit_width = !self.config.fit_width;
+                self.config.save();
+                self.compute_column_widths();
+                Command::none()
+            }
+            
+            Message::ToggleDensity => {
+                self.config.density = self.config.density.toggled();
+                self.config.save();
+                self.compute_column_widths();
+                Command::none()
+            }
+            
+            Message::ToggleClock => {
+                self.config.show_clock = !self.config.show_clock;
+                self.config.save();
+                Command::none()
+            }
+            
+            Message::ToggleSummary => {
+                self.config.show_summary = !self.config.show_summary;
+                self.config.save();
+                self.compute_column_widths();
+                Command::none()
+            }
+            
+            Message::SelectRow(index) => {
+                // Clicking the selected row again clears the selection
+                self.selected_row = if self.selected_row == Some(index) { None } else { Some(index) };
+                Command::none()
+            }
+            
+            Message::TogglePin(key) => {
+                // Favorites are keyed on the competitor, so they follow the row across refreshes
+                match self.config.favorites.iter().position(|favorite| *favorite == key) {
+                    Some(index) => { self.config.favorites.remove(index); }
+                    None => self.config.favorites.push(key),
+                }
+                self.config.save();
+                Command::none()
+            }
+            
+            Message::TogglePagination => {
+                self.paginate = !self.paginate;
+                self.page = 0;
+                Command::none()
+            }
+            
+            Message::NextPage => {
+                let rows = self.shown_data().map_or(0, |data| self.visible_rows(data).len());
+                self.page = (self.page + 1).min(self.page_count(rows) - 1);
+                Command::none()
+            }
+            
+            Message::PrevPage => {
+                self.page = self.page.saturating_sub(1);
+                Command::none()
+            }
+            
+            Message::ToggleAutoScroll => {
+                self.auto_scroll = !self.auto_scroll;
+                self.scroll_paused_until = None;
+                Command::none()
+            }
+            
+            Message::AutoScrollTick => {
+                let now = Instant::now();
+                if self.scroll_paused_until.map_or(false, |until| now < until) {
+                    return Command::none();
+                }
+                
+                // Pause at the bottom, then jump back to the top and pause again
+                if self.scroll_offset >= 1.0 {
+                    self.scroll_offset = 0.0;
+                    self.scroll_paused_until = Some(now + AUTO_SCROLL_PAUSE);
+                } else {
+                    let step = self.config.auto_scroll_speed * AUTO_SCROLL_TICK.as_secs_f32();
+                    self.scroll_offset = (self.scroll_offset + step / self.scrollable_height()).min(1.0);
+                    if self.scroll_offset >= 1.0 {
+                        self.scroll_paused_until = Some(now + AUTO_SCROLL_PAUSE);
+                    }
+                }
+                
+                self.snap_scroll()
+            }
+            
+            Message::HighlightTick => {
+                self.row_changes.retain(|_, (_, at)| at.elapsed() < HIGHLIGHT_DURATION);
+                Command::none()
+            }
+            
+            Message::Scrolled(viewport) => {
+                self.manual_scroll(viewport);
+                scrollable::snap_to(
+                    FROZEN_SCROLL.clone(),
+                    scrollable::RelativeOffset { x: 0.0, y: self.scroll_offset },
+                )
+            }
+            
+            Message::FrozenScrolled(viewport) => {
+                self.manual_scroll(viewport);
+                scrollable::snap_to(
+                    TABLE_SCROLL.clone(),
+                    scrollable::RelativeOffset { x: 0.0, y: self.scroll_offset },
+                )
+            }
+            
+            Message::WindowResized(width, height) => {
+                // The bars around the table keep their height, so the body takes the whole change
+                self.viewport_height = (self.viewport_height + height as f32 - self.window_height).max(0.0);
+                self.window_width = width as f32;
+                self.window_height = height as f32;
+                if self.config.fit_width {
+                    self.compute_column_widths();
+                }
+                
+                // Remembered for the next launch, but not while fullscreen, maximized or placed from the command line
+                if self.remember_window && !self.fullscreen && !self.maximized {
+                    self.config.window_size = (width, height);
+                }
+                Command::none()
+            }
+            
+            Message::WindowMoved(x, y) => {
+                if self.remember_window && !self.fullscreen && !self.maximized {
+                    self.config.window_position = Some((x, y));
+                }
+                Command::none()
+            }
+            
+            Message::ExportImage => {
+                if let Some(shown) = self.shown_data() {
+                    // The rows as drawn: pinned competitors first, then the sorted,
+                    // searched and faceted rest, cut to the row limit
+                    let pinned_rows = self.pinned_rows(shown);
+                    let is_pinned = |row: &Vec<String>| pinned_rows.iter().any(|pinned| std::ptr::eq(*pinned, row));
+                    let mut rows = pinned_rows.clone();
+                    let mut rest = self.visible_rows(shown);
+                    rest.retain(|row| !is_pinned(row));
+                    if let Some(max_rows) = self.config.max_rows {
+                        rest.truncate(max_rows);
+                    }
+                    rows.extend(rest);
+                    
+                    // Picked by index so each row keeps its key
+                    let indices: Vec<usize> = rows.iter()
+                        .filter_map(|row| shown.rows.iter().position(|candidate| std::ptr::eq(candidate, *row)))
+                        .collect();
+                    let data = shown.select_rows(&indices);
+                    let theme = self.theme.lock().unwrap().clone();
+                    let caption = format!("{} · {}", self.source_title(), Local::now().format("%d.%m.%Y %H:%M"));
+                    let (width, height) = (self.config.export_width, self.config.export_height);
+                    
+                    return Command::perform(
+                        async move {
+                            let path = match FileDialog::new()
+                                .add_filter("PNG Image", &["png"])
+                                .set_file_name("standings.png")
+                                .save_file()
+                            {
+                                Some(path) => path,
+                                None => return Ok(()),
+                            };
+                            export::export_png(&data, &theme, &caption, width, height, &path)
+                                .map_err(|err| err.to_string())
+                        },
+                        Message::ExportFinished
+                    );
+                }
+                Command::none()
+            }
+            
+            Message::ExportFinished(result) => {
+                if let Err(error) = result {
+                    self.error_message = Some(format!("Image export failed: {}", error));
+                }
+                Command::none()
+            }
+            
+            Message::FontLoaded => Command::none(),
+            
+            Message::Exit => {
+                // Persist the source, window geometry and theme before the window goes away
+                self.config.data_source = self.data_source.clone();
+                self.config.save();
+                
+                window::close()
+            }
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        // Create a subscription that emits a CheckForUpdates message every second
+        let updates = iced::time::every(Duration::from_secs(1))
+            .map(|_| Message::CheckForUpdates);
+        
+        let keys = iced::subscription::events_with(handle_event);
+        
+        let mut subscriptions = vec![updates, keys];
+        
+        if self.auto_scroll {
+            subscriptions.push(
+                iced::time::every(AUTO_SCROLL_TICK)
+                    .map(|_| Message::AutoScrollTick)
+            );
+        }
+        
+        // Any input wakes an idle display, so only listen while idling is configured
+        if self.config.idle_after().is_some() {
+            subscriptions.push(iced::subscription::events_with(input_activity));
+        }
+        
+        // Advance the signage playlist once each source's dwell time is up
+        if self.playlist.rotates() && !self.playlist.is_paused() {
+            subscriptions.push(
+                iced::time::every(Duration::from_secs(1))
+                    .map(|_| Message::PlaylistTick)
+            );
+        }
+        
+        // Redraw while change highlights are fading out
+        if !self.row_changes.is_empty() {
+            subscriptions.push(
+                iced::time::every(HIGHLIGHT_TICK)
+                    .map(|_| Message::HighlightTick)
+            );
+        }
+        
+        Subscription::batch(subscriptions)
+    }
+
+    fn view(&self) -> Element<Message> {
+        let theme = self.theme.lock().unwrap();
+        
+        // Main content area with table
+        let content = if let Some(data) = self.shown_data().filter(|data| !data.rows.is_empty()) {
+            let started = Instant::now();
+            let table = self.render_table(data, &theme);
+            log::debug!("render_table: {} rows in {:?}", data.rows.len(), started.elapsed());
+            table
+        } else {
+            // Tell setup problems apart from a source that simply has no results yet
+            let placeholder = match (&self.data_source, &self.last_data) {
+                (None, _) => labels::NO_SOURCE,
+                (Some(_), Some(_)) => labels::EMPTY_SHEET,
+                (Some(_), None) if self.is_loading => labels::LOADING,
+                (Some(_), None) => labels::NOT_LOADED,
+            };
+            
+            container(
+                text(placeholder)
+                    .size(self.scaled(24.0))
+                    .color(theme.fg)
+                    .horizontal_alignment(Horizontal::Center)
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                bg: theme.bg,
+            })))
+            .into()
+        };
+        
+        // Live search box, with a clear button while a filter is active
+        let mut search = row![
+            text_input(labels::SEARCH_PLACEHOLDER, &self.filter)
+                .on_input(Message::UpdateFilter)
+                .size(self.scaled(16.0))
+                .width(Length::Units(200))
+        ]
+        .spacing(5);
+        
+        if !self.filter.is_empty() {
+            search = search.push(
+                button(
+                    text(labels::CLEAR_SEARCH)
+                        .horizontal_alignment(Horizontal::Center)
+                        .size(self.scaled(16.0))
+                        .color(theme.footer_fg)
+                )
+                .on_press(Message::ClearFilter)
+                .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                    bg: theme.footer_bg,
+                    fg: theme.footer_fg,
+                    hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+                })))
+            );
+        }
+        
+        // One-click recovery while the source is failing
+        let mut recovery = row![];
+        if self.error_message.is_some() && self.data_source.is_some() {
+            recovery = recovery.push(
+                button(
+                    text(labels::RECONNECT)
+                        .horizontal_alignment(Horizontal::Center)
+                        .size(self.scaled(16.0))
+                        .color(theme.error_fg)
+                )
+                .on_press(Message::Reconnect)
+                .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                    bg: theme.error_bg,
+                    fg: theme.error_fg,
+                    hover_bg: mix(theme.error_bg, theme.error_fg, 0.2),
+                })))
+            );
+        }
+        
+        // Holding the standings, e.g. for the award ceremony
+        let freeze = button(
+            text(if self.updates_paused { labels::RESUME_UPDATES } else { labels::PAUSE_UPDATES })
+                .horizontal_alignment(Horizontal::Center)
+                .size(self.scaled(16.0))
+                .color(if self.updates_paused { theme.clock_fg } else { theme.footer_fg })
+        )
+        .on_press(Message::ToggleUpdatesPaused)
+        .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+            bg: if self.updates_paused { theme.clock_bg } else { theme.footer_bg },
+            fg: if self.updates_paused { theme.clock_fg } else { theme.footer_fg },
+            hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+        })));
+        
+        // Playlist controls, only while rotating through sources
+        let mut rotation = row![].spacing(5);
+        if self.playlist.rotates() {
+            let playlist_button = |label: &'static str, message: Message| {
+                button(
+                    text(label)
+                        .horizontal_alignment(Horizontal::Center)
+                        .size(self.scaled(16.0))
+                        .color(theme.footer_fg)
+                )
+                .on_press(message)
+                .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                    bg: theme.footer_bg,
+                    fg: theme.footer_fg,
+                    hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+                })))
+            };
+            
+            rotation = rotation
+                .push(playlist_button(
+                    if self.playlist.is_paused() { labels::PLAYLIST_RESUME } else { labels::PLAYLIST_PAUSE },
+                    Message::TogglePlaylistPause,
+                ))
+                .push(playlist_button(labels::PLAYLIST_NEXT, Message::SkipSource));
+        }
+        
+        // Page controls, only while paginating
+        let mut pager = row![].spacing(5);
+        
+        if self.paginate {
+            let rows = self.shown_data().map_or(0, |data| self.visible_rows(data).len());
+            let pages = self.page_count(rows);
+            let page_button = |label: &'static str, message: Message| {
+                button(
+                    text(label)
+                        .horizontal_alignment(Horizontal::Center)
+                        .size(self.scaled(16.0))
+                        .color(theme.footer_fg)
+                )
+                .on_press(message)
+                .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                    bg: theme.footer_bg,
+                    fg: theme.footer_fg,
+                    hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+                })))
+            };
+            
+            pager = pager
+                .push(page_button("◀", Message::PrevPage))
+                .push(
+                    text(format!("Page {}/{}", self.page.min(pages - 1) + 1, pages))
+                        .size(self.scaled(14.0))
+                        .color(theme.footer_fg)
+                )
+                .push(page_button("▶", Message::NextPage));
+        }
+        
+        // Refresh status, flagged when the table is the last good fetch rather than live data
+        let mut status_label = match (self.is_stale, self.last_updated) {
+            (true, Some(time)) => format!("{} {}", labels::STALE, time.format("%H:%M")),
+            (false, Some(time)) => format!("{} {}", labels::UPDATED, time.format("%H:%M:%S")),
+            _ => String::new(),
+        };
+        if self.is_loading {
+            status_label.push_str(labels::REFRESHING);
+        }
+        if self.updates_paused {
+            status_label = format!("{}  {}", labels::PAUSED, status_label);
+        }
+        
+        // How much of the field is loaded, and how much of it the search and facets show
+        let mut count_label = match self.shown_data() {
+            Some(data) => match self.visible_rows(data).len() {
+                shown if shown == data.rows.len() => format!("{} competitors", shown),
+                shown => format!("showing {} of {}", shown, data.rows.len()),
+            },
+            None => String::new(),
+        };
+        
+        // Flag rows that looked truncated in the source
+        if let Some(ref data) = self.last_data {
+            if data.repaired_rows > 0 || data.dropped_rows > 0 {
+                count_label.push_str(&format!(" ({} padded, {} dropped)", data.repaired_rows, data.dropped_rows));
+            }
+            if let Some((taken, ref snapshot)) = self.snapshot {
+                count_label.push_str(&format!(", {} {}", labels::COMPARED_WITH, taken.format("%H:%M")));
+                let missing = data.missing_since(snapshot);
+                if missing > 0 {
+                    count_label.push_str(&format!(" ({} {})", missing, labels::NO_LONGER_LISTED));
+                }
+            }
+        }
+        
+        // Branding caption and optional logo, configurable for other federations
+        let caption = self.config.footer_text.clone().unwrap_or_else(|| {
+            format!("{} v{}", labels::COPYRIGHT, VERSION)
+        });
+        let mut branding = row![].spacing(10).align_items(iced::Alignment::Center);
+        if let Some(ref logo) = self.config.logo_path {
+            branding = branding.push(
+                image(logo.clone())
+                    .height(Length::Units((30.0 * self.font_scale) as u16))
+            );
+        }
+        branding = branding.push(
+            text(caption)
+                .size(self.scaled(14.0))
+                .color(theme.footer_fg)
+        );
+        
+        // Footer with buttons
+        let footer = row![
+            branding,
+            Space::with_width(Length::Units(20)),
+            text(status_label)
+                .size(self.scaled(14.0))
+                .color(if self.is_loading {
+                    Color { a: 0.6, ..theme.footer_fg }
+                } else {
+                    theme.footer_fg
+                }),
+            Space::with_width(Length::Units(20)),
+            text(count_label)
+                .size(self.scaled(14.0))
+                .color(theme.footer_fg),
+            Space::with_width(Length::Fill),
+            recovery,
+            Space::with_width(Length::Units(10)),
+            freeze,
+            Space::with_width(Length::Units(10)),
+            rotation,
+            Space::with_width(Length::Units(10)),
+            pager,
+            Space::with_width(Length::Units(10)),
+            search,
+            Space::with_width(Length::Units(10)),
+            pick_list(
+                &ROW_LIMITS[..],
+                Some(RowLimit(self.config.max_rows)),
+                |limit| Message::SetRowLimit(limit.0)
+            )
+            .text_size(16),
+            Space::with_width(Length::Units(10)),
+            pick_list(
+                &REFRESH_PRESETS[..],
+                Some(RefreshPreset(self.refresh_interval)),
+                |preset| Message::SetRefreshInterval(preset.0)
+            )
+            .text_size(16),
+            Space::with_width(Length::Units(10)),
+            button(
+                text(labels::REFRESH)
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(16.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::ForceRefresh)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            }))),
+            Space::with_width(Length::Units(10)),
+            button(
+                text(if self.auto_scroll { labels::AUTO_SCROLL_STOP } else { labels::AUTO_SCROLL_START })
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(16.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::ToggleAutoScroll)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            }))),
+            Space::with_width(Length::Units(10)),
+            button(
+                text(labels::EXPORT)
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(16.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::ExportImage)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            }))),
+            Space::with_width(Length::Units(10)),
+            button(
+                text(labels::CLOUD)
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(16.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::ShowCloudDialog)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            }))),
+            Space::with_width(Length::Units(10)),
+            button(
+                text(labels::LOCAL)
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(16.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::OpenLocalFile)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            }))),
+            Space::with_width(Length::Units(10)),
+            button(
+                // Names the layout the button switches to
+                text(match self.config.density {
+                    Density::Comfortable => labels::DENSITY_COMPACT,
+                    Density::Compact => labels::DENSITY_COMFORTABLE,
+                })
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(16.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::ToggleDensity)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            }))),
+            Space::with_width(Length::Units(10)),
+            button(
+                text(labels::SUMMARY)
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(16.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::ToggleSummary)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            }))),
+            Space::with_width(Length::Units(10)),
+            button(
+                text(if self.snapshot.is_some() { labels::COMPARE_CLEAR } else { labels::COMPARE })
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(16.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::ToggleSnapshot)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            }))),
+            Space::with_width(Length::Units(10)),
+            button(
+                text(labels::CLOCK)
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(16.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::ToggleClock)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            }))),
+            Space::with_width(Length::Units(10)),
+            button(
+                text(labels::INFO)
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(16.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::ToggleInfo)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            }))),
+            Space::with_width(Length::Units(10)),
+            button(
+                text(labels::SETTINGS)
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(16.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::ToggleSettings)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            }))),
+            Space::with_width(Length::Units(10)),
+            button(
+                text(labels::THEME)
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(18.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::ToggleTheme)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            }))),
+            Space::with_width(Length::Units(10)),
+            // The window usually has no decorations, so this is the way out
+            button(
+                text(labels::QUIT)
+                    .horizontal_alignment(Horizontal::Center)
+                    .size(self.scaled(18.0))
+                    .color(theme.footer_fg)
+            )
+            .on_press(Message::Exit)
+            .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                bg: theme.footer_bg,
+                fg: theme.footer_fg,
+                hover_bg: Color::from_rgb(0.0, 0.26, 0.5),
+            })))
+        ]
+        .spacing(5)
+        .padding(10)
+        .width(Length::Fill)
+        .height(Length::Units((50.0 * self.font_scale) as u16))
+        .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+            bg: theme.footer_bg,
+        })));
+        
+        // Combine the main content, error banner and footer
+        let mut main_content = column![];
+        
+        // Event banner from the config, or else the sheet's title rows
+        let sheet = self.last_data.as_ref();
+        let title = self.playlist_title.clone()
+            .or_else(|| self.config.title.clone())
+            .or_else(|| sheet.and_then(|data| data.title.clone()));
+        let subtitle = self.config.subtitle.clone().or_else(|| sheet.and_then(|data| data.subtitle.clone()));
+        
+        if title.is_some() || subtitle.is_some() {
+            let mut banner = column![].spacing(2);
+            if let Some(title) = title {
+                banner = banner.push(
+                    text(title)
+                        .size(self.scaled(28.0))
+                        .color(theme.footer_fg)
+                        .font(Font { weight: font::Weight::Bold, ..self.ui_font })
+                );
+            }
+            if let Some(subtitle) = subtitle {
+                banner = banner.push(
+                    text(subtitle)
+                        .size(self.scaled(18.0))
+                        .color(theme.footer_fg)
+                );
+            }
+            
+            main_content = main_content.push(
+                container(banner)
+                    .width(Length::Fill)
+                    .padding(10)
+                    .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                        bg: theme.footer_bg,
+                    })))
+            );
+        }
+        
+        if self.config.show_clock {
+            main_content = main_content.push(
+                row![Space::with_width(Length::Fill), self.clock_view(&theme)]
+            );
+        }
+        
+        if self.sheet_names.len() > 1 {
+            main_content = main_content.push(self.tab_bar(&theme));
+        }
+        
+        // Summary panel beside the table, left out when the sheet can't support it
+        let summary = self.shown_data()
+            .filter(|_| self.config.show_summary)
+            .and_then(|data| self.summary_panel(data, &theme));
+        let content: Element<Message> = match summary {
+            Some(panel) => row![content, panel].height(Length::Fill).into(),
+            None => content,
+        };
+        
+        main_content = main_content.push(content);
+        
+        if let Some(ref error) = self.error_message {
+            // The last good table stays visible above the banner
+            let mut message = row![
+                text(error)
+                    .size(self.scaled(16.0))
+                    .color(theme.error_fg)
+                    .width(Length::Fill)
+            ];
+            if self.data_source.is_some() {
+                message = message.push(
+                    button(
+                        text(labels::RECONNECT)
+                            .size(self.scaled(16.0))
+                            .color(theme.error_fg)
+                    )
+                    .on_press(Message::Reconnect)
+                    .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                        bg: theme.error_bg,
+                        fg: theme.error_fg,
+                        hover_bg: mix(theme.error_bg, theme.error_fg, 0.2),
+                    })))
+                );
+            }
+            
+            let banner = container(message)
+            .width(Length::Fill)
+            .padding(10)
+            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                bg: theme.error_bg,
+            })));
+            
+            main_content = main_content.push(banner);
+        }
+        
+        let main_content = main_content.push(footer);
+        
+        // An idle display drifts by a few pixels each minute within a small margin,
+        // which an active one doesn't need
+        let padding = match (self.idle_since, self.config.idle_mode) {
+            (Some(since), IdleMode::Shift) => {
+                let (shift_x, shift_y) = IDLE_SHIFTS[(since.elapsed().as_secs() / 60) as usize % IDLE_SHIFTS.len()];
+                [shift_y, IDLE_SHIFT_MAX - shift_x, IDLE_SHIFT_MAX - shift_y, shift_x]
+            }
+            _ => [0; 4],
+        };
+        
+        let main_view = container(main_content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(padding)
+            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                bg: theme.bg,
+            })));
+        
+        // Cloud dialog floats over the dimmed table, clicking beside it cancels
+        if self.cloud_dialog_open {
+            return Modal::new(main_view, self.cloud_dialog_view(&theme))
+                .on_blur(Message::CloseCloudDialog)
+                .into();
+        }
+        
+        if self.help_open {
+            return self.help_view(&theme);
+        }
+        
+        if self.settings_open {
+            return self.settings_view(&theme);
+        }
+        
+        if self.idle_since.is_some() && self.config.idle_mode == IdleMode::Dim {
+            return Modal::new(main_view, Space::with_width(Length::Units(0)))
+                .dim(IDLE_DIM)
+                .into();
+        }
+        
+        if let (Some(column), Some(data)) = (self.facet_open, self.shown_data()) {
+            return self.facet_view(data, column, &theme);
+        }
+        
+        if self.info_open {
+            return self.info_view(&theme);
+        }
+        
+        main_view.into()
+    }
+}
+
+impl ScoreViewer {
+    // Quietly list the sheets of the entered spreadsheet so the sheet name can be checked
+    // Whether a URL still points at the spreadsheet typed in the cloud dialog
+    fn is_current_spreadsheet(&self, url: &str) -> bool {
+        CloudHandler::extract_spreadsheet_id(url).ok() == self.sheets_id.as_deref()
+    }
+    
+    fn prefetch_sheets(&self) -> Command<Message> {
+        if CloudHandler::extract_spreadsheet_id(&self.cloud_url_input).is_err() {
+            return Command::none();
+        }
+        
+        let url = self.cloud_url_input.trim().to_string();
+        let cloud = self.cloud.clone();
+        
+        Command::perform(
+            async move {
+                let sheets = match cloud.list_sheets(&url).await {
+                    Ok(sheets) => Some(sheets),
+                    Err(err) => {
+                        log::debug!("Sheet names not checked: {}", err);
+                        None
+                    }
+                };
+                (url, sheets)
+            },
+            |(url, sheets)| Message::SheetsPrefetched(url, sheets),
+        )
+    }
+    
+    // Height of the scrollable range, used to turn pixels into a relative offset
+    fn scrollable_height(&self) -> f32 {
+        let rows = self.shown_data().map_or(0, |data| self.body_rows(data).0.len());
+        (rows as f32 * self.row_height() - self.viewport_height).max(1.0)
+    }
+    
+    // The key of the body row at the top of the view and how far past its top the
+    // view is, None at the very top so new leaders still show up there
+    fn scroll_anchor(&self) -> Option<(String, f32)> {
+        let data = self.shown_data().filter(|_| self.scroll_offset > 0.0)?;
+        let pixels = self.scroll_offset * self.scrollable_height();
+        let (rows, _) = self.body_rows(data);
+        let position = ((pixels / self.row_height()) as usize).min(rows.len().checked_sub(1)?);
+        let index = data.rows.iter().position(|row| std::ptr::eq(row, rows[position]))?;
+        
+        Some((data.row_key(index), pixels - position as f32 * self.row_height()))
+    }
+    
+    // The relative offset that puts an anchored row back at the top of the view
+    fn anchored_offset(&self, (key, past_top): &(String, f32)) -> Option<f32> {
+        let data = self.shown_data()?;
+        let index = data.row_keys().iter().position(|candidate| candidate == key)?;
+        let position = self.body_rows(data).0.iter().position(|row| std::ptr::eq(*row, &data.rows[index]))?;
+        
+        Some(((position as f32 * self.row_height() + past_top) / self.scrollable_height()).clamp(0.0, 1.0))
+    }
+    
+    fn set_dark_mode(&mut self, dark: bool) {
+        self.is_dark_mode = dark;
+        *self.theme.lock().unwrap() = if dark {
+            DARK_THEME.clone()
+        } else {
+            LIGHT_THEME.clone()
+        };
+    }
+    
+    // Dim or drift the display once it has sat unchanged and untouched for the configured time
+    fn check_idle(&mut self) {
+        let due = self.config.idle_after()
+            .map_or(false, |after| self.last_activity.elapsed() >= after);
+        if due && self.idle_since.is_none() {
+            log::info!("Display idle, protecting the screen");
+            self.idle_since = Some(Instant::now());
+        }
+    }
+    
+    // The table as drawn: hidden columns left out, and a column of rank changes
+    // while a snapshot is kept. Rows stay in the order of last_data.
+    fn shown_data(&self) -> Option<&TableData> {
+        self.shown.as_ref()
+    }
+    
+    // Work out the table as drawn once per new table or snapshot rather than every frame
+    fn update_shown(&mut self) {
+        self.shown = self.last_data.as_ref().map(|data| {
+            let shown = data.visible_view();
+            match self.snapshot {
+                Some((_, ref snapshot)) => shown.with_rank_deltas(snapshot),
+                None => shown,
+            }
+        });
+        
+        // A sort on a column the table no longer has, like the comparison column
+        // once the snapshot is cleared, is dropped
+        if let Some(shown) = self.shown.as_ref().filter(|shown| !shown.headers.is_empty()) {
+            if matches!(self.sort, Some((column, _)) if column >= shown.headers.len()) {
+                self.sort = None;
+            }
+        }
+    }
+    
+    // Errors and test results don't outlive the dialog
+    fn close_cloud_dialog(&mut self) {
+        self.cloud_dialog_open = false;
+        self.cloud_dialog_error = None;
+        self.cloud_test_result = None;
+    }
+    
+    fn close_settings(&mut self) {
+        self.settings_open = false;
+        self.config.save();
+    }
+    
+    fn note_activity(&mut self) {
+        self.last_activity = Instant::now();
+        self.idle_since = None;
+    }
+    
+    // Follow the day/night schedule, switching only when the scheduled mode changes
+    // so a manual toggle holds until the next switch. Custom themes are left alone.
+    fn apply_theme_schedule(&mut self) {
+        if self.config.theme_path.is_some() {
+            return;
+        }
+        let dark = match self.config.scheduled_dark_mode(Local::now().hour()) {
+            Some(dark) => dark,
+            None => return,
+        };
+        if self.scheduled_dark != Some(dark) {
+            self.scheduled_dark = Some(dark);
+            self.set_dark_mode(dark);
+        }
+    }
+    
+    // Move both table panes to the current scroll offset
+    fn snap_scroll(&self) -> Command<Message> {
+        Command::batch(vec![
+            scrollable::snap_to(
+                TABLE_SCROLL.clone(),
+                scrollable::RelativeOffset { x: 0.0, y: self.scroll_offset },
+            ),
+            scrollable::snap_to(
+                FROZEN_SCROLL.clone(),
+                scrollable::RelativeOffset { x: 0.0, y: self.scroll_offset },
+            ),
+        ])
+    }
+    
+    fn manual_scroll(&mut self, viewport: scrollable::Viewport) {
+        self.viewport_height = viewport.bounds().height;
+        
+        // Manual scrolling takes over for a while before auto-scroll resumes
+        self.scroll_offset = viewport.relative_offset().y;
+        if self.auto_scroll {
+            self.scroll_paused_until = Some(Instant::now() + MANUAL_SCROLL_PAUSE);
+        }
+    }
+    
+    // Estimated height of one table row: text line, cell padding and row spacing
+    fn row_height(&self) -> f32 {
+        self.cell_text_size() * 1.3 + self.config.density.cell_padding() as f32 * 2.0 + 1.0
+    }
+    
+    fn cell_text_size(&self) -> f32 {
+        self.scaled(self.config.density.text_size())
+    }
+    
+    fn scaled(&self, size: f32) -> f32 {
+        size * self.font_scale
+    }
+    
+    // Short name of the current source, used as the export caption
+    fn source_title(&self) -> String {
+        match &self.data_source {
+            Some(DataSource::Local(path)) => path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            Some(DataSource::Files(paths)) => paths.iter()
+                .filter_map(|path| path.file_stem())
+                .map(|stem| stem.to_string_lossy().to_string())
+                .collect::<Vec<String>>()
+                .join(" + "),
+            Some(DataSource::Cloud(_, _)) => {
+                let sheet = self.selected_sheet();
+                if sheet.is_empty() { "Results".to_string() } else { sheet }
+            }
+            Some(DataSource::Url(url)) => url.rsplit('/')
+                .next()
+                .and_then(|name| Path::new(name).file_stem())
+                .map(|stem| stem.to_string_lossy().to_string())
+                .filter(|stem| !stem.is_empty())
+                .unwrap_or_else(|| "Results".to_string()),
+            None => "Results".to_string(),
+        }
+    }
+    
+    // Keep sort and filters in the config so the display reopens as it was left
+    fn save_view_state(&mut self) {
+        self.config.sort = self.sort;
+        self.config.filter = self.filter.clone();
+        self.config.facets = self.facets.clone();
+        self.config.save();
+    }
+    
+    fn set_data(&mut self, data: TableData) {
+        // Follow the selected competitor to wherever the refresh put them
+        let selected_key = match (self.selected_row, &self.last_data) {
+            (Some(index), Some(last)) if index < last.rows.len() => Some(last.row_key(index)),
+            _ => None,
+        };
+        self.selected_row = selected_key
+            .and_then(|key| data.row_keys().into_iter().position(|candidate| candidate == key));
+        
+        self.last_data = Some(data);
+        self.update_shown();
+        
+        // Column indices below are into the table as drawn, comparison column included
+        if let Some(shown) = self.shown.as_ref().filter(|shown| !shown.headers.is_empty()) {
+            // Find result and rank column indices
+            self.result_column_index = shown.result_column();
+            self.rank_column_index = shown.rank_column();
+        }
+        
+        self.compute_column_widths();
+    }
+    
+    // Size each column to its longest cell, estimated from the scaled font size
+    fn compute_column_widths(&mut self) {
+        self.column_widths.clear();
+        
+        let char_width = self.char_width();
+        let max_width = self.scaled(MAX_COLUMN_WIDTH);
+        
+        // Measured on the table as drawn, including any comparison column
+        let data = match self.shown_data() {
+            Some(data) => data,
+            None => return,
+        };
+        self.column_widths = data.headers.iter().enumerate().map(|(i, header)| {
+            // Leave room for the sort indicator next to the header
+            let longest = data.rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .chain(std::iter::once(header.chars().count() + 2))
+                .max()
+                .unwrap_or(0);
+            
+            let width = (longest as f32 * char_width + self.config.density.cell_padding() as f32 * 2.0).min(max_width);
+            width as u16
+        }).collect();
+        
+        // Fall back to the fixed widths if the estimate is unusable
+        if self.column_widths.iter().all(|w| *w <= 10) {
+            self.column_widths.clear();
+            return;
+        }
+        
+        // Share out the window width in proportion to the content estimates
+        if self.config.fit_width {
+            let summary_width = if self.config.show_summary { self.summary_width() as f32 } else { 0.0 };
+            let spacing = self.column_widths.len() as f32;
+            let available = self.window_width - summary_width - spacing;
+            let total: f32 = self.column_widths.iter().map(|w| *w as f32).sum();
+            
+            if available > 0.0 && total > 0.0 {
+                let scale = available / total;
+                for width in self.column_widths.iter_mut() {
+                    *width = (*width as f32 * scale).floor() as u16;
+                }
+            }
+        }
+    }
+    
+    fn column_width(&self, column: usize) -> Length {
+        Length::Units(self.column_width_units(column))
+    }
+    
+    fn column_width_units(&self, column: usize) -> u16 {
+        match self.column_widths.get(column) {
+            Some(width) => *width,
+            None if column == 0 => 150,
+            None => 100,
+        }
+    }
+    
+    // Rough width of one character of table text
+    fn char_width(&self) -> f32 {
+        self.cell_text_size() * 0.6
+    }
+    
+    fn select_local(&mut self, paths: Vec<PathBuf>) {
+        self.load_id += 1;
+        self.record_local_state(&paths);
+        self.pending_local = None;
+        self.data_source = Some(match <[PathBuf; 1]>::try_from(paths) {
+            Ok([path]) => DataSource::Local(path),
+            Err(paths) => DataSource::Files(paths),
+        });
+    }
+    
+    fn record_local_state(&mut self, paths: &[PathBuf]) {
+        self.local_states = paths.iter().map(|path| local_state(path)).collect();
+    }
+    
+    // Whether any of the watched files changed since they were last read
+    fn local_files_changed(&self, paths: &[PathBuf]) -> bool {
+        paths.iter().enumerate()
+            .any(|(i, path)| file_changed(path, self.local_states.get(i).copied().unwrap_or_default()))
+    }
+    
+    fn select_cloud(&mut self, url: String, sheets: Vec<String>) {
+        self.load_id += 1;
+        self.poll_slowdown = 1;
+        self.unchanged_polls = 0;
+        self.poll_paused_until = None;
+        self.spreadsheet_url = url.clone();
+        self.sheet_names = sheets.clone();
+        self.selected_tab = 0;
+        self.tab_data = vec![None; sheets.len()];
+        self.data_source = Some(DataSource::Cloud(url, sheets));
+    }
+    
+    fn select_url(&mut self, url: String) {
+        self.load_id += 1;
+        self.sheet_names.clear();
+        self.tab_data.clear();
+        self.selected_tab = 0;
+        self.url_validators = Validators::default();
+        self.data_source = Some(DataSource::Url(url));
+    }
+    
+    // Switch to a source and start loading it
+    fn open_source(&mut self, source: DataSource) -> Command<Message> {
+        // A snapshot of another competition means nothing here
+        self.snapshot = None;
+        self.update_shown();
+        
+        match source {
+            DataSource::Local(path) => {
+                self.select_local(vec![path.clone()]);
+                self.load_local(vec![path])
+            }
+            DataSource::Files(paths) => {
+                self.select_local(paths.clone());
+                self.load_local(paths)
+            }
+            DataSource::Cloud(url, sheets) => {
+                self.select_cloud(url, sheets);
+                self.load_all_tabs()
+            }
+            DataSource::Url(url) => {
+                self.select_url(url.clone());
+                self.load_url(url)
+            }
+        }
+    }
+    
+    fn show_playlist_entry(&mut self, entry: PlaylistEntry) -> Command<Message> {
+        self.playlist_title = entry.title;
+        self.sort = None;
+        self.scroll_offset = 0.0;
+        self.open_source(entry.source)
+    }
+    
+    // A source picked by hand stays on screen until the rotation is resumed
+    fn hold_playlist(&mut self) {
+        self.playlist_title = None;
+        self.playlist.pause();
+    }
+    
+    // How often the source is actually polled; cloud sources adapt to stay within quota
+    fn effective_interval(&self) -> Duration {
+        match self.data_source {
+            Some(DataSource::Cloud(_, _)) => self.refresh_interval * self.poll_slowdown,
+            _ => self.refresh_interval,
+        }
+    }
+    
+    fn selected_sheet(&self) -> String {
+        self.sheet_names.get(self.selected_tab).cloned().unwrap_or_default()
+    }
+    
+    fn load_local(&mut self, mut paths: Vec<PathBuf>) -> Command<Message> {
+        self.is_loading = true;
+        let id = self.load_id;
+        let columns = self.column_config.clone();
+        
+        // Several files are merged by the CSV reader, which checks their columns match
+        if paths.len() > 1 {
+            return Command::perform(
+                async move { CSVHandler::new(columns).read_csv_files(paths).await.map_err(|err| err.to_string()) },
+                move |result| match result {
+                    Ok(data) => Message::DataUpdated(id, data),
+                    Err(err) => Message::LoadFailed(id, err),
+                }
+            );
+        }
+        let path = match paths.pop() {
+            Some(path) => path,
+            None => return Command::none(),
+        };
+        
+        // Pick the reader from the file extension, CSV unless it's a workbook
+        let is_xlsx = path.extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("xlsx"));
+        
+        Command::perform(
+            async move {
+                if is_xlsx {
+                    let xlsx_handler = XlsxHandler::new(columns);
+                    xlsx_handler.read_xlsx(path).await.map_err(|err| err.to_string())
+                } else {
+                    let csv_handler = CSVHandler::new(columns);
+                    csv_handler.read_csv(path).await.map_err(|err| err.to_string())
+                }
+            },
+            move |result| match result {
+                Ok(data) => Message::DataUpdated(id, data),
+                Err(err) => Message::LoadFailed(id, err),
+            }
+        )
+    }
+    
+    fn load_all_tabs(&mut self) -> Command<Message> {
+        let mut commands = Vec::new();
+        for tab in 0..self.sheet_names.len() {
+            commands.push(self.load_cloud(self.spreadsheet_url.clone(), tab));
+        }
+        Command::batch(commands)
+    }
+    
+    fn load_cloud(&mut self, url: String, tab: usize) -> Command<Message> {
+        self.is_loading = true;
+        let id = self.load_id;
+        let sheet = self.sheet_names.get(tab).cloned().unwrap_or_default();
+        let cloud = self.cloud.clone();
+        
+        Command::perform(
+            async move { cloud.fetch_data(&url, &sheet).await },
+            move |result| match result {
+                Ok(data) => Message::SheetUpdated(id, tab, data),
+                Err(FetchError::RateLimited(retry_after)) => Message::RateLimited(id, retry_after),
+                Err(err) => Message::CloudError(id, err.to_string()),
+            }
+        )
+    }
+    
+    fn load_url(&mut self, url: String) -> Command<Message> {
+        self.is_loading = true;
+        let id = self.load_id;
+        let columns = self.column_config.clone();
+        let timeout = self.config.request_timeout();
+        let validators = self.url_validators.clone();
+        
+        Command::perform(
+            async move {
+                UrlHandler::new(columns).with_timeout(timeout)
+                    .fetch(&url, &validators).await
+                    .map_err(|err| err.to_string())
+            },
+            move |result| match result {
+                Ok(fetch) => Message::UrlFetched(id, fetch),
+                Err(err) => Message::LoadFailed(id, format!("Download failed: {}", err)),
+            }
+        )
+    }
+    
+    fn tab_bar(&self, theme: &Styles) -> Element<Message> {
+        Row::with_children(
+            self.sheet_names.iter().enumerate().map(|(i, sheet)| {
+                let bg = if i == self.selected_tab { theme.footer_bg } else { theme.header_bg };
+                let fg = if i == self.selected_tab { theme.footer_fg } else { theme.header_fg };
+                
+                button(
+                    text(sheet)
+                        .size(self.scaled(16.0))
+                        .color(fg)
+                )
+                .on_press(Message::SelectTab(i))
+                .padding(8)
+                .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                    bg,
+                    fg,
+                    hover_bg: theme.footer_bg,
+                })))
+                .into()
+            })
+            .collect()
+        )
+        .spacing(2)
+        .padding(5)
+        .into()
+    }
+    
+    // Rows in display order: sorted first, then filtered so the order stays stable while typing
+    // Rows of the competitors pinned as favorites, in table order
+    fn pinned_rows<'a>(&self, data: &'a TableData) -> Vec<&'a Vec<String>> {
+        data.rows.iter()
+            .zip(data.row_keys())
+            .filter(|(_, key)| self.config.favorites.contains(key))
+            .map(|(row, _)| row)
+            .collect()
+    }
+    
+    // The rows of the scrolling body as drawn: searched, faceted and sorted, pinned
+    // ones left out, cut to the row limit and the current page. Also how many rows
+    // the row limit cut.
+    fn body_rows<'a>(&self, data: &'a TableData) -> (Vec<&'a Vec<String>>, usize) {
+        let pinned_rows = self.pinned_rows(data);
+        let mut rows = self.visible_rows(data);
+        rows.retain(|row| !pinned_rows.iter().any(|pinned| std::ptr::eq(*pinned, *row)));
+        
+        // A deliberate cut for presentation, unlike pagination the rest is never shown
+        let mut cut = 0;
+        if let Some(max_rows) = self.config.max_rows {
+            cut = rows.len().saturating_sub(max_rows);
+            rows.truncate(max_rows);
+        }
+        
+        // Only materialize the current page when paginating
+        if self.paginate {
+            let page = self.page.min(self.page_count(rows.len()) - 1);
+            let start = page * self.config.page_size;
+            let end = (start + self.config.page_size).min(rows.len());
+            rows = rows[start..end].to_vec();
+        }
+        
+        (rows, cut)
+    }
+    
+    fn visible_rows<'a>(&self, data: &'a TableData) -> Vec<&'a Vec<String>> {
+        let sorted_rows = match self.sort {
+            Some((column, dir)) => data.sorted_rows(column, dir),
+            None => data.rows.iter().collect(),
+        };
+        
+        // Facets on columns the current table doesn't have are ignored, and values
+        // match regardless of case, as distinct_values lists them
+        let facets: Vec<(usize, BTreeSet<String>)> = self.facets.iter()
+            .filter_map(|(column, values)| {
+                Some((data.column_index(column)?, values.iter().map(|value| value.to_lowercase()).collect()))
+            })
+            .collect();
+        let in_facets = |row: &Vec<String>| facets.iter().all(|(column, values)| {
+            row.get(*column).map_or(false, |cell| values.contains(&cell.trim().to_lowercase()))
+        });
+        
+        let filter = self.filter.to_lowercase();
+        sorted_rows.into_iter()
+            .filter(|row| in_facets(row))
+            .filter(|row| filter.is_empty() || row.iter().any(|cell| cell.to_lowercase().contains(&filter)))
+            .collect()
+    }
+    
+    fn page_count(&self, rows: usize) -> usize {
+        let page_size = self.config.page_size.max(1);
+        ((rows + page_size - 1) / page_size).max(1)
+    }
+    
+    fn render_table(&self, data: &TableData, theme: &Styles) -> Element<Message> {
+        let header_cell = |i: usize, header: &String| -> Element<Message> {
+            let label = match self.sort {
+                Some((column, SortDir::Ascending)) if column == i => format!("{} ▲", header),
+                Some((column, SortDir::Descending)) if column == i => format!("{} ▼", header),
+                _ => header.clone(),
+            };
+            
+            // The arrow opens the facet filter, highlighted while one is active
+            let filtered = self.facets.contains_key(header.trim());
+            container(
+                row![
+                    button(
+                        text(label)
+                            .size(self.cell_text_size())
+                            .color(theme.header_fg)
+                    )
+                    .on_press(Message::SortBy(i))
+                    .padding(0)
+                    .width(Length::Fill)
+                    .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                        bg: theme.header_bg,
+                        fg: theme.header_fg,
+                        hover_bg: theme.header_bg,
+                    }))),
+                    button(
+                        text("▾")
+                            .size(self.cell_text_size())
+                            .color(if filtered { theme.result_fg } else { theme.header_fg })
+                    )
+                    .on_press(Message::OpenFacet(i))
+                    .padding(0)
+                    .style(iced::theme::Button::Custom(Box::new(ButtonStyle {
+                        bg: theme.header_bg,
+                        fg: theme.header_fg,
+                        hover_bg: theme.header_bg,
+                    }))),
+                ]
+            )
+            .width(self.column_width(i))
+            .padding(self.config.density.cell_padding())
+            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                bg: theme.header_bg,
+            })))
+            .into()
+        };
+        
+        // Pinned competitors sit above the scrolling body, whatever their rank or the search
+        let pinned_rows = self.pinned_rows(data);
+        let is_pinned = |row: &Vec<String>| pinned_rows.iter().any(|pinned| std::ptr::eq(*pinned, row));
+        
+        let (visible_rows, hidden_rows) = self.body_rows(data);
+        
+        // Only build the rows inside the viewport, with spacers standing in for the rest
+        let row_height = self.row_height();
+        let total = visible_rows.len();
+        let content_height = total as f32 * row_height;
+        let scroll_y = self.scroll_offset * (content_height - self.viewport_height).max(0.0);
+        let first = ((scroll_y / row_height) as usize).saturating_sub(VIRTUAL_OVERSCAN).min(total);
+        let count = (self.viewport_height / row_height) as usize + 2 * VIRTUAL_OVERSCAN;
+        let last = (first + count).min(total);
+        let top_space = first as f32 * row_height;
+        let bottom_space = (total - last) as f32 * row_height;
+        
+        let numeric_columns = data.numeric_columns();
+        
+        // Rules can read hidden columns, which only the full table has
+        let full = self.last_data.as_ref();
+        
+        let body_cell = |row: &[String], index: Option<usize>, i: usize, cell: &String, medal: Option<Color>, striped: bool, selected: bool, flash: Option<(Color, f32)>| -> Element<Message> {
+            let is_result_column = self.result_column_index.map_or(false, |idx| idx == i);
+            let is_numeric = numeric_columns.get(i).copied().unwrap_or(false);
+            
+            // Conditional colors from the column config take precedence over the defaults
+            let (rule_bg, rule_fg) = data.headers.get(i)
+                .and_then(|header| self.column_config.rule_for(header, |name| {
+                    full.zip(index).and_then(|(full, index)| full.named_cell(index, name))
+                }))
+                .map_or((None, None), |rule| rule.colors());
+            
+            // Rank changes read green going up and red going down
+            let rule_fg = rule_fg.or_else(|| match data.headers.get(i) {
+                Some(header) if header == DELTA_HEADER && cell.starts_with('▲') => Some(theme.valid),
+                Some(header) if header == DELTA_HEADER && cell.starts_with('▼') => Some(theme.invalid),
+                _ => None,
+            });
+            
+            let mut font = if is_numeric { Font::MONOSPACE } else { self.ui_font };
+            if is_result_column {
+                font.weight = font::Weight::Bold;
+            }
+            
+            // Numbers are rounded for display only; decorated labels such as pinned names are left alone
+            let formatted = data.headers.get(i)
+                .filter(|_| matches!(data.column_type(i), ColumnType::Integer | ColumnType::Float))
+                .filter(|_| row.get(i) == Some(cell))
+                .and_then(|header| self.column_config.format_cell(header, data.value(row, i)));
+            let cell = formatted.as_ref().unwrap_or(cell);
+            
+            // Long cells are shortened to the column unless it wraps
+            let overflow = data.headers.get(i)
+                .map_or(Overflow::Ellipsis, |header| self.column_config.overflow_for(header));
+            let room = self.column_width_units(i) as f32 - self.config.density.cell_padding() as f32 * 2.0;
+            let max_chars = ((room / self.char_width()) as usize).max(1);
+            let shortened = match overflow {
+                Overflow::Wrap => None,
+                Overflow::Ellipsis => truncate(cell, max_chars, true),
+                Overflow::Clip => truncate(cell, max_chars, false),
+            };
+            
+            let content = container(
+                text(shortened.as_deref().unwrap_or(cell))
+                    .size(self.cell_text_size())
+                    .color(rule_fg.unwrap_or(if is_result_column { theme.result_fg } else { theme.fg }))
+                    .width(Length::Fill)
+                    .horizontal_alignment(if is_numeric { Horizontal::Right } else { Horizontal::Left })
+                    .font(font)
+            )
+            .width(self.column_width(i))
+            .padding(self.config.density.cell_padding())
+            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                bg: {
+                    let bg = match (rule_bg, medal) {
+                        _ if selected => theme.selected,
+                        (Some(color), _) => color,
+                        (None, Some(color)) => color,
+                        (None, None) if is_result_column => theme.result_bg,
+                        (None, None) if striped => theme.stripe,
+                        (None, None) => theme.bg,
+                    };
+                    flash.map_or(bg, |(color, strength)| mix(bg, color, strength))
+                },
+            })));
+            
+            // Hovering a shortened cell shows the full value
+            if overflow == Overflow::Ellipsis && shortened.is_some() {
+                tooltip(content, cell, tooltip::Position::FollowCursor)
+                    .size(self.cell_text_size())
+                    .padding(5)
+                    .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                        bg: theme.header_bg,
+                    })))
+                    .into()
+            } else {
+                content.into()
+            }
+        };
+        
+        let spacer = |height: f32| -> Element<Message> {
+            Space::with_height(Length::Units(height.min(u16::MAX as f32) as u16)).into()
+        };
+        
+        // The frozen first cell and the scrolling rest of a row
+        let table_row = |row: &Vec<String>, position: usize| -> (Element<Message>, Element<Message>) {
+            let striped = self.config.striped_rows && position % 2 == 1;
+            
+            // Podium rows get a medal background
+            let medal = self.rank_column_index
+                .filter(|_| self.config.medals)
+                .and_then(|idx| row.get(idx))
+                .and_then(|rank| parse_rank(rank))
+                .and_then(|rank| match rank {
+                    1 => Some(theme.gold),
+                    2 => Some(theme.silver),
+                    3 => Some(theme.bronze),
+                    _ => None,
+                });
+            
+            // Rows are compared by address, since row keys needn't be unique
+            let index = data.rows.iter().position(|candidate| std::ptr::eq(candidate, row));
+            let selected = index.is_some() && index == self.selected_row;
+            let key = index.map(|index| data.row_key(index)).unwrap_or_default();
+            
+            // Recently changed rows fade from the highlight back to their normal colors
+            let flash = self.row_changes.get(&key).map(|(change, at)| {
+                let color = match change {
+                    RowChange::Changed => theme.changed,
+                    RowChange::Added => theme.added,
+                };
+                (color, 1.0 - at.elapsed().as_secs_f32() / HIGHLIGHT_DURATION.as_secs_f32())
+            });
+            
+            // Clicking the first cell pins or unpins the competitor
+            let frozen: Element<Message> = match row.first() {
+                Some(cell) => {
+                    let label = if is_pinned(row) { format!("★ {}", cell) } else { cell.clone() };
+                    button(body_cell(row, index, 0, &label, medal, striped, selected, flash))
+                        .on_press(Message::TogglePin(key))
+                        .padding(0)
+                        .style(iced::theme::Button::Text)
+                        .into()
+                }
+                None => spacer(row_height),
+            };
+            // The rest of the row selects it, to help keep one's place when reading aloud
+            let mut body = button(
+                Row::with_children(
+                    row.iter().enumerate().skip(1).map(|(i, cell)| body_cell(row, index, i, cell, medal, striped, selected, flash)).collect()
+                )
+                .spacing(1)
+            )
+            .padding(0)
+            .style(iced::theme::Button::Text);
+            if let Some(index) = index {
+                body = body.on_press(Message::SelectRow(index));
+            }
+            let body = body.into();
+            
+            (frozen, body)
+        };
+        
+        // The first column is pinned on the left, the rest scroll horizontally
+        let mut frozen_cells = vec![spacer(top_space)];
+        let mut body_rows = vec![spacer(top_space)];
+        
+        for (position, row) in visible_rows.iter().copied().enumerate().take(last).skip(first) {
+            let (frozen, body) = table_row(row, position);
+            frozen_cells.push(frozen);
+            body_rows.push(body);
+        }
+        
+        let mut pinned_frozen = Vec::new();
+        let mut pinned_body = Vec::new();
+        for (position, row) in pinned_rows.into_iter().enumerate() {
+            let (frozen, body) = table_row(row, position);
+            pinned_frozen.push(frozen);
+            pinned_body.push(body);
+        }
+        
+        frozen_cells.push(spacer(bottom_space));
+        body_rows.push(spacer(bottom_space));
+        
+        // Headers sit outside the vertical scrollables so they stay visible; both use the same widths
+        let frozen_header: Element<Message> = match data.headers.first() {
+            Some(header) => header_cell(0, header),
+            None => Space::with_width(Length::Shrink).into(),
+        };
+        let headers = Row::with_children(
+            data.headers.iter().enumerate().skip(1).map(|(i, header)| header_cell(i, header)).collect()
+        )
+        .spacing(1);
+        
+        // Scrolling either pane snaps the other to the same offset
+        let frozen = column![
+            frozen_header,
+            Column::with_children(pinned_frozen).spacing(1),
+            scrollable(Column::with_children(frozen_cells).spacing(1))
+                .id(FROZEN_SCROLL.clone())
+                .on_scroll(Message::FrozenScrolled)
+                .direction(scrollable::Direction::Vertical(
+                    scrollable::Properties::new().width(0).scroller_width(0)
+                ))
+                .height(Length::Fill),
+        ]
+        .spacing(1);
+        
+        let body = scrollable(Column::with_children(body_rows).spacing(1))
+            .id(TABLE_SCROLL.clone())
+            .on_scroll(Message::Scrolled)
+            .height(Length::Fill);
+        
+        let wide = scrollable(
+            column![headers, Column::with_children(pinned_body).spacing(1), body]
+                .spacing(1)
+                .height(Length::Fill)
+        )
+        .direction(scrollable::Direction::Horizontal(scrollable::Properties::default()))
+        .height(Length::Fill);
+        
+        let table = row![frozen, wide].spacing(1);
+        if hidden_rows == 0 {
+            return table.into();
+        }
+        
+        column![
+            table.height(Length::Fill),
+            container(
+                text(format!("+ {} {}", hidden_rows, labels::MORE_ROWS))
+                    .size(self.scaled(14.0))
+                    .color(Color { a: 0.6, ..theme.fg })
+            )
+            .padding(self.config.density.cell_padding())
+        ]
+        .into()
+    }
+    
+    // Current time, and the countdown when one is set. Redrawn by the one-second
+    // update tick; once the target passes, "TIME" blinks with each tick.
+    fn clock_view(&self, theme: &Styles) -> Element<Message> {
+        let now = Local::now();
+        let mut clock = column![
+            text(now.format("%H:%M:%S").to_string())
+                .size(self.scaled(28.0))
+                .color(theme.clock_fg)
+                .font(Font::MONOSPACE),
+        ]
+        .align_items(iced::Alignment::End);
+        
+        let target = self.config.countdown_to.as_deref().and_then(clock::parse_target);
+        if let Some(target) = target {
+            let countdown = match clock::remaining_secs(now.time(), target) {
+                Some(secs) => text(clock::format_remaining(secs)).color(theme.clock_fg),
+                None if now.second() % 2 == 0 => text(labels::COUNTDOWN_OVER).color(theme.error_fg),
+                None => text(labels::COUNTDOWN_OVER).color(theme.clock_bg),
+            };
+            clock = clock.push(countdown.size(self.scaled(22.0)).font(Font::MONOSPACE));
+        }
+        
+        container(clock)
+            .padding(8)
+            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                bg: theme.clock_bg,
+            })))
+            .into()
+    }
+    
+    fn summary_width(&self) -> u16 {
+        (240.0 * self.font_scale) as u16
+    }
+    
+    // Per-category competitor count, best and average result
+    fn summary_panel(&self, data: &TableData, theme: &Styles) -> Option<Element<Message>> {
+        let summaries = data.category_summaries();
+        if summaries.is_empty() && data.totals.is_empty() {
+            return None;
+        }
+        
+        let size = self.scaled(15.0);
+        let line = |label: &str, value: String| {
+            row![
+                text(label).size(size).color(theme.fg).width(Length::Fill),
+                text(value).size(size).color(theme.result_fg),
+            ]
+        };
+        
+        let mut panel = column![].spacing(12);
+        for summary in summaries {
+            panel = panel.push(
+                column![
+                    text(summary.category)
+                        .size(self.scaled(17.0))
+                        .color(theme.header_fg)
+                        .font(Font { weight: font::Weight::Bold, ..self.ui_font }),
+                    line(labels::SUMMARY_COMPETITORS, summary.competitors.to_string()),
+                    line(labels::SUMMARY_BEST, summary.best.unwrap_or_else(|| "-".to_string())),
+                    line(labels::SUMMARY_AVERAGE, summary.average.unwrap_or_else(|| "-".to_string())),
+                ]
+                .spacing(2)
+            );
+        }
+        
+        // Totals rows stripped from the sheet, shown by column
+        for totals in data.totals.iter() {
+            let mut block = column![
+                text(labels::SUMMARY_TOTALS)
+                    .size(self.scaled(17.0))
+                    .color(theme.header_fg)
+                    .font(Font { weight: font::Weight::Bold, ..self.ui_font }),
+            ]
+            .spacing(2);
+            for (header, cell) in data.headers.iter().zip(totals.iter()) {
+                if !cell.trim().is_empty() {
+                    block = block.push(line(header, cell.clone()));
+                }
+            }
+            panel = panel.push(block);
+        }
+        
+        Some(
+            container(scrollable(panel))
+                .width(Length::Units(self.summary_width()))
+                .height(Length::Fill)
+                .padding(10)
+                .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                    bg: theme.header_bg,
+                })))
+                .into()
+        )
+    }
+    
     fn cloud_dialog_view(&self, theme: &Styles) -> Element<Message> {
+        // Shown under the URL field when it can't be used
+        let url_error: Element<Message> = match self.cloud_dialog_error {
+            Some(ref error) => container(
+                text(error)
+                    .size(14)
+                    .color(theme.error_fg)
+            )
+            .padding(5)
+            .width(Length::Units(400))
+            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                bg: theme.error_bg,
+            })))
+            .into(),
+            None => Space::with_height(Length::Units(0)).into(),
+        };
+        
+        // Outcome of the last connection test
+        let test_result: Element<Message> = match self.cloud_test_result {
+            Some(Ok(ref summary)) => text(summary)
+                .size(14)
+                .color(theme.fg)
+                .into(),
+            Some(Err(ref error)) => container(
+                text(format!("Test failed: {}", error))
+                    .size(14)
+                    .color(theme.error_fg)
+            )
+            .padding(5)
+            .width(Length::Units(400))
+            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                bg: theme.error_bg,
+            })))
+            .into(),
+            None => Space::with_height(Length::Units(0)).into(),
+        };
+        
+        // Checked against the listed sheets, blank when there is nothing to check against
+        let sheet_check: Element<Message> = if self.available_sheets.is_empty() || self.cloud_sheet_input.trim().is_empty() {
+            Space::with_width(Length::Units(20)).into()
+        } else {
+            match unknown_sheets(&self.cloud_sheet_input, &self.available_sheets).as_slice() {
+                [] => text("✓")
+                    .size(20)
+                    .color(theme.valid)
+                    .width(Length::Units(20))
+                    .into(),
+                unknown => tooltip(
+                    text("✗")
+                        .size(20)
+                        .color(theme.invalid)
+                        .width(Length::Units(20)),
+                    format!("No sheet named {}", unknown.join(", ")),
+                    tooltip::Position::Bottom,
+                )
+                .size(14)
+                .padding(5)
+                .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                    bg: theme.header_bg,
+                })))
+                .into(),
+            }
+        };
+        
         let dialog_content = column![
             text("Connect to Google Sheet")
                 .size(24)
@@ -434,14 +2947,43 @@ impl ScoreViewer {
                 .padding(10)
                 .width(Length::Units(400))
                 .on_input(Message::UpdateCloudUrl),
+            url_error,
+            Space::with_height(Length::Units(10)),
+            text("Sheet Names (optional, comma-separated):")
+                .size(16)
+                .color(theme.fg),
+            row![
+                iced::widget::text_input(&self.cloud_sheet_input, "Enter Sheet Name")
+                    .padding(10)
+                    .width(Length::Units(370))
+                    .on_input(Message::UpdateSheetName),
+                Space::with_width(Length::Units(10)),
+                sheet_check,
+            ]
+            .align_items(iced::Alignment::Center),
+            row![
+                pick_list(
+                    self.available_sheets.clone(),
+                    self.available_sheets.iter().find(|sheet| **sheet == self.cloud_sheet_input).cloned(),
+                    Message::PickSheet
+                )
+                .placeholder("Choose a sheet")
+                .width(Length::Units(280)),
+                Space::with_width(Length::Units(10)),
+                button(text("List sheets").size(16))
+                    .on_press(Message::ListSheets)
+                    .padding(10)
+                    .width(Length::Units(110)),
+            ],
             Space::with_height(Length::Units(10)),
-            text("Sheet Name (optional):")
+            text("Or a CSV file URL:")
                 .size(16)
                 .color(theme.fg),
-            iced::widget::text_input(&self.cloud_sheet_input, "Enter Sheet Name")
+            iced::widget::text_input(&self.cloud_csv_input, "https://example.com/results.csv")
                 .padding(10)
                 .width(Length::Units(400))
-                .on_input(Message::UpdateSheetName),
+                .on_input(Message::UpdateCsvUrl),
+            test_result,
             Space::with_height(Length::Units(20)),
             row![
                 button(text("Connect").size(16))
@@ -449,6 +2991,11 @@ impl ScoreViewer {
                     .padding(10)
                     .width(Length::Units(100)),
                 Space::with_width(Length::Units(20)),
+                button(text("Test").size(16))
+                    .on_press(Message::TestConnection)
+                    .padding(10)
+                    .width(Length::Units(100)),
+                Space::with_width(Length::Units(20)),
                 button(text("Cancel").size(16))
                     .on_press(Message::CloseCloudDialog)
                     .padding(10)
@@ -457,17 +3004,276 @@ impl ScoreViewer {
         ]
         .spacing(10)
         .padding(20)
-        .width(Length::Units(450))
-        .height(Length::Units(300))
+        .width(Length::Units(480))
+        .height(Length::Units(540))
         .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
             bg: theme.bg,
         })));
         
-        let dialog = container(dialog_content)
-            .width(Length::Units(450))
-            .height(Length::Units(300))
+        // The dim background is drawn by the modal in view
+        container(dialog_content)
+            .width(Length::Units(480))
+            .height(Length::Units(540))
+            .center_x()
+            .center_y()
+            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                bg: theme.bg,
+            })))
+            .into()
+    }
+    
+    // Troubleshooting details about the running viewer and its source
+    fn info_view(&self, theme: &Styles) -> Element<Message> {
+        let source = match self.data_source {
+            Some(DataSource::Local(ref path)) => format!("Local file: {}", path.display()),
+            Some(DataSource::Files(ref paths)) => {
+                let paths: Vec<String> = paths.iter().map(|path| path.display().to_string()).collect();
+                format!("Local files: {}", paths.join(", "))
+            }
+            Some(DataSource::Cloud(ref url, ref sheets)) => {
+                let sheets: Vec<&str> = sheets.iter()
+                    .map(|sheet| if sheet.is_empty() { "(first sheet)" } else { sheet.as_str() })
+                    .collect();
+                format!("Google Sheet: {}\nSheets: {}", url, sheets.join(", "))
+            }
+            Some(DataSource::Url(ref url)) => format!("CSV URL: {}", url),
+            None => "No source selected".to_string(),
+        };
+        
+        let credentials = match self.config.credentials_path() {
+            Some(path) => path.display().to_string(),
+            None => "credentials.json (default)".to_string(),
+        };
+        
+        let last_update = match self.last_updated {
+            Some(time) => time.format("%d.%m.%Y %H:%M:%S").to_string(),
+            None => "never".to_string(),
+        };
+        
+        let size = match self.last_data {
+            Some(ref data) if data.has_hidden_columns() => {
+                let hidden = data.visible.iter().filter(|visible| !**visible).count();
+                format!("{} rows, {} columns ({} hidden)", data.rows.len(), data.headers.len() - hidden, hidden)
+            }
+            Some(ref data) => format!("{} rows, {} columns", data.rows.len(), data.headers.len()),
+            None => "no data".to_string(),
+        };
+        
+        let last_error = match self.last_error {
+            Some((time, ref error)) => format!("{} at {}", error, time.format("%H:%M:%S")),
+            None => "none".to_string(),
+        };
+        
+        let mut polling = format!("every {}s", self.effective_interval().as_secs());
+        if let Some(until) = self.poll_paused_until.filter(|until| *until > Instant::now()) {
+            polling.push_str(&format!(", paused {}s for quota", (until - Instant::now()).as_secs()));
+        }
+        
+        let details = [
+            ("Version", VERSION.to_string()),
+            ("Source", source),
+            ("Polling", polling),
+            ("Credentials", credentials),
+            ("Last update", last_update),
+            ("Table", size),
+            ("Last error", last_error),
+        ];
+        
+        let rows = details.into_iter().map(|(label, value)| {
+            row![
+                text(label)
+                    .size(16)
+                    .color(theme.fg)
+                    .width(Length::Units(120)),
+                text(value)
+                    .size(16)
+                    .color(theme.fg)
+            ]
+            .into()
+        });
+        
+        let info_content = column![
+            text("About")
+                .size(24)
+                .color(theme.fg),
+            Space::with_height(Length::Units(10)),
+            Column::with_children(rows.collect()).spacing(5)
+        ]
+        .spacing(10)
+        .padding(20);
+        
+        let dialog = container(info_content)
+            .width(Length::Units(600))
+            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                bg: theme.bg,
+            })));
+        
+        // Overlay dialog on top of dimmed background
+        container(dialog)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .style(iced::theme::Container::Custom(Box::new(OverlayStyle {})))
+            .into()
+    }
+    
+    // Presentation options in one place, applied live and saved on close
+    fn settings_view(&self, theme: &Styles) -> Element<Message> {
+        let options = [
+            ("Dark theme", self.is_dark_mode, Message::ToggleTheme),
+            ("Compact layout", self.config.density == Density::Compact, Message::ToggleDensity),
+            ("Fit columns to the window", self.config.fit_width, Message::ToggleFitWidth),
+            ("Striped rows", self.config.striped_rows, Message::ToggleStripes),
+            ("Medal colors for the podium", self.config.medals, Message::ToggleMedals),
+            ("Highlight changed rows", self.config.change_highlights, Message::ToggleChangeHighlights),
+            ("Category summary", self.config.show_summary, Message::ToggleSummary),
+            ("Clock", self.config.show_clock, Message::ToggleClock),
+            ("Auto-scroll", self.auto_scroll, Message::ToggleAutoScroll),
+        ];
+        
+        let checkboxes = options.into_iter().map(|(label, checked, message)| {
+            checkbox(label, checked, move |_| message.clone())
+                .size(16)
+                .text_size(16)
+                .into()
+        });
+        
+        let settings_content = column![
+            text("Display Settings")
+                .size(24)
+                .color(theme.fg),
+            Column::with_children(checkboxes.collect()).spacing(8),
+            Space::with_height(Length::Units(5)),
+            text(format!("Text size: {:.0}%", self.font_scale * 100.0))
+                .size(16)
+                .color(theme.fg),
+            iced::widget::slider(MIN_FONT_SCALE..=MAX_FONT_SCALE, self.font_scale, Message::SetFontScale)
+                .step(FONT_SCALE_STEP),
+            Space::with_height(Length::Units(5)),
+            button(text("Done").size(16))
+                .on_press(Message::ToggleSettings)
+                .padding(10),
+        ]
+        .spacing(10)
+        .padding(20);
+        
+        let dialog = container(settings_content)
+            .width(Length::Units(360))
+            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                bg: theme.bg,
+            })));
+        
+        // Overlay dialog on top of dimmed background
+        container(dialog)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .style(iced::theme::Container::Custom(Box::new(OverlayStyle {})))
+            .into()
+    }
+    
+    // Tick the values of one column to show; ticking none shows every row
+    fn facet_view(&self, data: &TableData, column: usize, theme: &Styles) -> Element<Message> {
+        let header = data.headers.get(column).map(|header| header.trim().to_string()).unwrap_or_default();
+        let selected = self.facets.get(&header);
+        
+        let values = data.distinct_values(column).into_iter().map(|value| {
+            let checked = selected.map_or(false, |values| values.iter().any(|ticked| ticked.to_lowercase() == value.to_lowercase()));
+            let (column, toggled) = (header.clone(), value.clone());
+            checkbox(value, checked, move |_| Message::ToggleFacetValue(column.clone(), toggled.clone()))
+                .size(16)
+                .text_size(16)
+                .into()
+        });
+        
+        let facet_content = column![
+            text(format!("Show {}", header))
+                .size(24)
+                .color(theme.fg),
+            scrollable(Column::with_children(values.collect()).spacing(5))
+                .height(Length::Units(360)),
+            row![
+                button(text("Show all").size(16))
+                    .on_press(Message::ClearFacet(header.clone()))
+                    .padding(10),
+                Space::with_width(Length::Units(10)),
+                button(text("Done").size(16))
+                    .on_press(Message::CloseFacet)
+                    .padding(10),
+            ],
+        ]
+        .spacing(10)
+        .padding(20);
+        
+        let dialog = container(facet_content)
+            .width(Length::Units(360))
+            .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
+                bg: theme.bg,
+            })));
+        
+        // Overlay dialog on top of dimmed background
+        container(dialog)
+            .width(Length::Fill)
+            .height(Length::Fill)
             .center_x()
             .center_y()
+            .style(iced::theme::Container::Custom(Box::new(OverlayStyle {})))
+            .into()
+    }
+    
+    fn help_view(&self, theme: &Styles) -> Element<Message> {
+        let bindings = [
+            ("T", "Toggle light/dark theme"),
+            ("R", "Refresh now"),
+            ("O", "Open a local file"),
+            ("C", "Connect to Google Sheets"),
+            ("L", "Load a custom theme"),
+            ("Space", "Start or stop auto-scroll"),
+            ("P", "Toggle pagination"),
+            ("D", "Toggle compact layout"),
+            ("W", "Fit columns to the window width"),
+            ("I", "Show source and version details"),
+            ("K", "Show or hide the clock"),
+            ("S", "Display settings"),
+            ("M", "Compare positions with now, or stop comparing"),
+            ("U", "Pause or resume updates, e.g. for the ceremony"),
+            ("PgUp / PgDn", "Previous / next page"),
+            ("Ctrl + / -", "Zoom in / out"),
+            ("F11", "Toggle fullscreen"),
+            ("F10", "Toggle window decorations"),
+            ("Esc", "Close dialog / clear search"),
+            ("Click name", "Pin or unpin a competitor at the top"),
+            ("Ctrl + Q", "Quit"),
+            ("?", "Show or hide this help"),
+        ];
+        
+        let rows = bindings.iter().map(|(key, action)| {
+            row![
+                text(*key)
+                    .size(16)
+                    .color(theme.fg)
+                    .width(Length::Units(100)),
+                text(*action)
+                    .size(16)
+                    .color(theme.fg)
+            ]
+            .into()
+        });
+        
+        let help_content = column![
+            text("Keyboard Shortcuts")
+                .size(24)
+                .color(theme.fg),
+            Space::with_height(Length::Units(10)),
+            Column::with_children(rows.collect()).spacing(5)
+        ]
+        .spacing(10)
+        .padding(20);
+        
+        let dialog = container(help_content)
+            .width(Length::Units(400))
             .style(iced::theme::Container::Custom(Box::new(ContainerStyle { 
                 bg: theme.bg,
             })));
@@ -483,6 +3289,158 @@ impl ScoreViewer {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowChange {
+    Changed,
+    Added,
+}
+
+// Rows of the new table that are new or differ from the old one, by row key
+fn diff_rows(old: &TableData, new: &TableData) -> Vec<(String, RowChange)> {
+    let previous: HashMap<String, &Vec<String>> = old.row_keys().into_iter()
+        .zip(&old.rows)
+        .collect();
+    
+    new.row_keys().into_iter()
+        .zip(&new.rows)
+        .filter_map(|(key, row)| {
+            match previous.get(&key) {
+                Some(old_row) if *old_row == row => None,
+                Some(_) => Some((key, RowChange::Changed)),
+                None => Some((key, RowChange::Added)),
+            }
+        })
+        .collect()
+}
+
+// The Sheets client for this launch, built once from the config
+fn cloud_client(config: &AppConfig, columns: ColumnConfig) -> Arc<dyn CloudClient> {
+    if config.demo {
+        return Arc::new(MockCloudHandler::new(columns));
+    }
+    
+    let mut cloud_handler = CloudHandler::new(columns).with_timeout(config.request_timeout());
+    if let Some(path) = config.credentials_path() {
+        cloud_handler = cloud_handler.with_credentials(path);
+    }
+    Arc::new(cloud_handler)
+}
+
+// Summary shown after a successful connection test
+fn describe_table(data: &TableData) -> String {
+    format!("Connection OK: {} rows, {} columns", data.rows.len(), data.headers.len())
+}
+
+// Modification time, size and content hash of a local file
+type FileState = (Option<std::time::SystemTime>, Option<u64>, Option<u64>);
+type FileStamp = (Option<std::time::SystemTime>, Option<u64>);
+
+fn local_state(path: &Path) -> FileState {
+    let (modified, size) = file_stamp(path);
+    (modified, size, file_hash(path))
+}
+
+// Whether a file differs from its recorded state. A newer mtime or another size
+// is a change without further ado; only a file that looks untouched is hashed,
+// once per refresh interval, to catch rewrites that keep the timestamp or land
+// within a coarse one.
+fn file_changed(path: &Path, (last_modified, last_size, last_hash): FileState) -> bool {
+    let (modified, size) = file_stamp(path);
+    if size.is_none() {
+        return false;  // Missing for now, e.g. mid-replace
+    }
+    if size != last_size || modified > last_modified {
+        return true;
+    }
+    file_hash(path).map_or(false, |hash| Some(hash) != last_hash)
+}
+
+// Modification time and size, which cost no read of the file
+fn file_stamp(path: &Path) -> FileStamp {
+    let metadata = fs::metadata(path).ok();
+    (metadata.as_ref().and_then(|m| m.modified().ok()), metadata.map(|m| m.len()))
+}
+
+// Whether changed files look the same as on the previous check, otherwise
+// remembering how they look now for the next one
+fn settled(pending: &mut Option<Vec<FileStamp>>, stamps: Vec<FileStamp>) -> bool {
+    if pending.as_ref() == Some(&stamps) {
+        *pending = None;
+        true
+    } else {
+        *pending = Some(stamps);
+        false
+    }
+}
+
+// Fast non-cryptographic hash of a file's contents
+fn file_hash(path: &Path) -> Option<u64> {
+    let bytes = fs::read(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+// Keyboard, mouse and touch input, which ends the idle dim
+fn input_activity(event: Event, _status: event::Status) -> Option<Message> {
+    match event {
+        Event::Keyboard(keyboard::Event::KeyPressed { .. })
+        | Event::Mouse(iced::mouse::Event::CursorMoved { .. })
+        | Event::Mouse(iced::mouse::Event::ButtonPressed(_))
+        | Event::Mouse(iced::mouse::Event::WheelScrolled { .. })
+        | Event::Touch(_) => Some(Message::UserActivity),
+        _ => None,
+    }
+}
+
+// Map global keyboard shortcuts and window geometry changes to messages
+fn handle_event(event: Event, status: event::Status) -> Option<Message> {
+    let (key_code, modifiers) = match event {
+        Event::Window(window::Event::Resized { width, height }) => {
+            return Some(Message::WindowResized(width, height));
+        }
+        Event::Window(window::Event::Moved { x, y }) => {
+            return Some(Message::WindowMoved(x, y));
+        }
+        Event::Window(window::Event::CloseRequested) => {
+            return Some(Message::Exit);
+        }
+        Event::Keyboard(keyboard::Event::KeyPressed { key_code, modifiers }) => (key_code, modifiers),
+        Event::Keyboard(keyboard::Event::CharacterReceived('?')) if status == event::Status::Ignored => {
+            return Some(Message::ToggleHelp);
+        }
+        _ => return None,
+    };
+    
+    match key_code {
+        KeyCode::Equals | KeyCode::Plus | KeyCode::NumpadAdd if modifiers.control() => Some(Message::ZoomIn),
+        KeyCode::Minus | KeyCode::NumpadSubtract if modifiers.control() => Some(Message::ZoomOut),
+        KeyCode::Q if modifiers.control() => Some(Message::Exit),
+        KeyCode::Escape => Some(Message::EscapePressed),
+        KeyCode::F11 => Some(Message::ToggleFullscreen),
+        KeyCode::F10 => Some(Message::ToggleDecorations),
+        // Letter shortcuts must not fire while typing into a text input
+        _ if status == event::Status::Captured => None,
+        KeyCode::T => Some(Message::ToggleTheme),
+        KeyCode::R => Some(Message::ForceRefresh),
+        KeyCode::O => Some(Message::OpenLocalFile),
+        KeyCode::C => Some(Message::ShowCloudDialog),
+        KeyCode::L => Some(Message::LoadTheme),
+        KeyCode::Space => Some(Message::ToggleAutoScroll),
+        KeyCode::P => Some(Message::TogglePagination),
+        KeyCode::D => Some(Message::ToggleDensity),
+        KeyCode::W => Some(Message::ToggleFitWidth),
+        KeyCode::I => Some(Message::ToggleInfo),
+        KeyCode::K => Some(Message::ToggleClock),
+        KeyCode::S => Some(Message::ToggleSettings),
+        KeyCode::M => Some(Message::ToggleSnapshot),
+        KeyCode::U => Some(Message::ToggleUpdatesPaused),
+        KeyCode::PageDown => Some(Message::NextPage),
+        KeyCode::PageUp => Some(Message::PrevPage),
+        _ => None,
+    }
+}
+
 // Custom styles for containers and buttons
 struct ContainerStyle {
     bg: Color,
@@ -540,3 +3498,166 @@ impl button::StyleSheet for ButtonStyle {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Frame time with and without virtualization on the 1000-row fixture, run with
+    // SVR_LOG=debug cargo test --release render_time -- --ignored
+    #[test]
+    #[ignore]
+    fn render_time() {
+        logging::init();
+        let fixture = include_bytes!("../tests/fixtures/1000-rows.csv");
+        let data = CSVHandler::parse_bytes(fixture, &ColumnConfig::default()).unwrap();
+        let (mut viewer, _) = ScoreViewer::new((AppConfig::default(), cli::WindowArgs::default()));
+        viewer.set_data(data);
+        
+        let frame_time = |viewer: &ScoreViewer| {
+            const FRAMES: u32 = 50;
+            let started = Instant::now();
+            for _ in 0..FRAMES {
+                drop(viewer.view());
+            }
+            started.elapsed() / FRAMES
+        };
+        
+        // A 1080p window only builds the rows in view
+        viewer.viewport_height = 1080.0;
+        let virtualized = frame_time(&viewer);
+        
+        // A viewport as tall as the table builds every row, as before virtualization
+        viewer.viewport_height = 1000.0 * viewer.row_height();
+        let every_row = frame_time(&viewer);
+        
+        log::debug!("1000 rows: {:?} per frame virtualized, {:?} building every row", virtualized, every_row);
+        assert!(virtualized < every_row);
+    }
+
+    #[test]
+    fn reads_files_only_once_they_hold_still() {
+        let at = |secs| Some(std::time::SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+        let mut pending = None;
+        
+        // The export tool writes twice in quick succession
+        assert!(!settled(&mut pending, vec![(at(100), Some(512))]));
+        assert!(!settled(&mut pending, vec![(at(101), Some(2048))]));
+        
+        // Same mtime and size on the next tick, so the file is read
+        assert!(settled(&mut pending, vec![(at(101), Some(2048))]));
+        assert_eq!(pending, None);
+    }
+
+    #[test]
+    fn a_later_write_restarts_the_settle_delay() {
+        let at = |secs| Some(std::time::SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+        let mut pending = None;
+        
+        assert!(!settled(&mut pending, vec![(at(100), Some(2048)), (at(90), Some(64))]));
+        assert!(!settled(&mut pending, vec![(at(100), Some(2048)), (at(102), Some(64))]));
+        assert!(settled(&mut pending, vec![(at(100), Some(2048)), (at(102), Some(64))]));
+    }
+
+    #[test]
+    fn same_size_rewrite_with_the_same_mtime_is_a_change() {
+        let path = std::env::temp_dir().join(format!("svr-rewrite-{}.csv", std::process::id()));
+        fs::write(&path, "name,result\nEero,624.3\n").unwrap();
+        let state = local_state(&path);
+        assert!(!file_changed(&path, state));
+        
+        // Same length, and the timestamp put back as an export tool might
+        fs::write(&path, "name,result\nEero,625.1\n").unwrap();
+        fs::File::options().write(true).open(&path).unwrap().set_modified(state.0.unwrap()).unwrap();
+        let changed = file_changed(&path, state);
+        fs::remove_file(&path).ok();
+        
+        assert!(changed);
+    }
+
+    #[test]
+    fn newer_mtime_or_other_size_is_a_change() {
+        let path = std::env::temp_dir().join(format!("svr-stamp-{}.csv", std::process::id()));
+        fs::write(&path, "name,result\n").unwrap();
+        let (modified, size, _) = local_state(&path);
+        
+        let older = modified.map(|modified| modified - Duration::from_secs(10));
+        assert!(file_changed(&path, (older, size, None)));
+        assert!(file_changed(&path, (modified, size.map(|size| size + 1), None)));
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn scroll_range_counts_only_the_rows_drawn() {
+        let rows: String = (1..=100).map(|i| format!("M,Name{},{}\n", i, 500 + i)).collect();
+        let csv = format!("category,first_name,result\n{}", rows);
+        let (mut viewer, _) = ScoreViewer::new((AppConfig::default(), cli::WindowArgs::default()));
+        viewer.set_data(CSVHandler::parse_bytes(csv.as_bytes(), &ColumnConfig::default()).unwrap());
+        viewer.viewport_height = 10.0 * viewer.row_height();
+        
+        let rows_past_the_viewport = |viewer: &ScoreViewer| (viewer.scrollable_height() / viewer.row_height()).round();
+        assert_eq!(rows_past_the_viewport(&viewer), 90.0);
+        
+        // Name1, Name10 to Name19 and Name100
+        viewer.filter = "name1".to_string();
+        viewer.viewport_height = 2.0 * viewer.row_height();
+        assert_eq!(rows_past_the_viewport(&viewer), 10.0);
+        
+        viewer.config.max_rows = Some(5);
+        assert_eq!(rows_past_the_viewport(&viewer), 3.0);
+    }
+
+    #[test]
+    fn editing_the_url_keeps_the_sheets_of_the_same_spreadsheet() {
+        let (mut viewer, _) = ScoreViewer::new((AppConfig::default(), cli::WindowArgs::default()));
+        let url = "https://docs.google.com/spreadsheets/d/1AbCdEfGhIjKlMnOpQrStUvWxYz/edit";
+        let _ = viewer.update(Message::UpdateCloudUrl(url.to_string()));
+        viewer.available_sheets = vec!["Rifle".to_string()];
+        
+        let _ = viewer.update(Message::UpdateCloudUrl(format!("{}#gid=0", url)));
+        assert_eq!(viewer.available_sheets, vec!["Rifle"]);
+        
+        // A partly typed ID is a different spreadsheet
+        let _ = viewer.update(Message::UpdateCloudUrl(url.replace("WxYz", "WxY")));
+        assert!(viewer.available_sheets.is_empty());
+        assert!(!viewer.is_current_spreadsheet(url));
+    }
+
+    #[test]
+    fn clearing_the_snapshot_drops_a_sort_on_the_comparison_column() {
+        let csv = "category,first_name,result\nM,Eero,624.3\nM,Mikko,622.0\n";
+        let (mut viewer, _) = ScoreViewer::new((AppConfig::default(), cli::WindowArgs::default()));
+        viewer.set_data(CSVHandler::parse_bytes(csv.as_bytes(), &ColumnConfig::default()).unwrap());
+        
+        let _ = viewer.update(Message::ToggleSnapshot);
+        let delta_column = viewer.shown_data().unwrap().headers.len() - 1;
+        viewer.sort = Some((delta_column, SortDir::Ascending));
+        
+        // As ToggleSnapshot clears it, short of saving the config
+        viewer.snapshot = None;
+        viewer.update_shown();
+        assert_eq!(viewer.sort, None);
+        assert!(viewer.shown_data().unwrap().headers.len() <= delta_column);
+    }
+
+    #[test]
+    fn refresh_keeps_the_top_row_in_place() {
+        let table = |new: usize| {
+            let rows: String = (1..=new).map(|i| format!("M,New{},{}\n", i, 700 + i))
+                .chain((1..=100).map(|i| format!("M,Name{},{}\n", i, 500 + i)))
+                .collect();
+            CSVHandler::parse_bytes(format!("category,first_name,result\n{}", rows).as_bytes(), &ColumnConfig::default()).unwrap()
+        };
+        let (mut viewer, _) = ScoreViewer::new((AppConfig::default(), cli::WindowArgs::default()));
+        viewer.set_data(table(0));
+        viewer.viewport_height = 10.0 * viewer.row_height();
+        
+        // Name51 at the top, a third of a row scrolled past
+        let rows_from_the_top = |viewer: &ScoreViewer| viewer.scroll_offset * viewer.scrollable_height() / viewer.row_height();
+        viewer.scroll_offset = 50.33 * viewer.row_height() / viewer.scrollable_height();
+        
+        // Ten new rows above push Name51 down by ten
+        let _ = viewer.update(Message::DataUpdated(viewer.load_id, table(10)));
+        assert!((rows_from_the_top(&viewer) - 60.33).abs() < 0.01);
+    }
+}
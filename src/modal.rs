@@ -0,0 +1,292 @@
+// src/modal.rs
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::overlay;
+use iced::advanced::renderer;
+use iced::advanced::widget::{self, Widget};
+use iced::advanced::{self, Clipboard, Shell};
+use iced::alignment::Alignment;
+use iced::event;
+use iced::mouse;
+use iced::{Color, Element, Event, Length, Point, Rectangle, Size};
+
+// Same dim as the other full-window overlays
+const DEFAULT_DIM: f32 = 0.7;
+
+// Shows `dialog` centered over `base`, which stays visible but dimmed behind it
+pub struct Modal<'a, Message, Renderer> {
+    base: Element<'a, Message, Renderer>,
+    dialog: Element<'a, Message, Renderer>,
+    on_blur: Option<Message>,
+    dim: f32,
+}
+
+impl<'a, Message, Renderer> Modal<'a, Message, Renderer> {
+    pub fn new(
+        base: impl Into<Element<'a, Message, Renderer>>,
+        dialog: impl Into<Element<'a, Message, Renderer>>,
+    ) -> Self {
+        Modal {
+            base: base.into(),
+            dialog: dialog.into(),
+            on_blur: None,
+            dim: DEFAULT_DIM,
+        }
+    }
+
+    // Message published when the dimmed area outside the dialog is clicked
+    pub fn on_blur(mut self, message: Message) -> Self {
+        self.on_blur = Some(message);
+        self
+    }
+
+    // Opacity of the black drawn over the base, from 0.0 to 1.0
+    pub fn dim(mut self, alpha: f32) -> Self {
+        self.dim = alpha.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for Modal<'a, Message, Renderer>
+where
+    Renderer: advanced::Renderer,
+    Message: Clone,
+{
+    fn children(&self) -> Vec<widget::Tree> {
+        vec![widget::Tree::new(&self.base), widget::Tree::new(&self.dialog)]
+    }
+
+    fn diff(&self, tree: &mut widget::Tree) {
+        tree.diff_children(&[&self.base, &self.dialog]);
+    }
+
+    fn width(&self) -> Length {
+        self.base.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.base.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.base.as_widget().layout(renderer, limits)
+    }
+
+    // The base only gets events the overlay lets through, so it can't be clicked while the dialog is up
+    fn on_event(
+        &mut self,
+        state: &mut widget::Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.base.as_widget_mut().on_event(
+            &mut state.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn draw(
+        &self,
+        state: &widget::Tree,
+        renderer: &mut Renderer,
+        theme: &<Renderer as advanced::Renderer>::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.base.as_widget().draw(
+            &state.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        state: &'b mut widget::Tree,
+        layout: Layout<'_>,
+        _renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        Some(overlay::Element::new(
+            layout.position(),
+            Box::new(Overlay {
+                content: &mut self.dialog,
+                tree: &mut state.children[1],
+                size: layout.bounds().size(),
+                on_blur: self.on_blur.clone(),
+                dim: self.dim,
+            }),
+        ))
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &widget::Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.base.as_widget().mouse_interaction(
+            &state.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn operate(
+        &self,
+        state: &mut widget::Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.base.as_widget().operate(&mut state.children[0], layout, renderer, operation);
+    }
+}
+
+struct Overlay<'a, 'b, Message, Renderer> {
+    content: &'b mut Element<'a, Message, Renderer>,
+    tree: &'b mut widget::Tree,
+    size: Size,
+    on_blur: Option<Message>,
+    dim: f32,
+}
+
+impl<'a, 'b, Message, Renderer> overlay::Overlay<Message, Renderer> for Overlay<'a, 'b, Message, Renderer>
+where
+    Renderer: advanced::Renderer,
+    Message: Clone,
+{
+    // Covers the whole window with the dialog centered in it
+    fn layout(&self, renderer: &Renderer, _bounds: Size, position: Point) -> layout::Node {
+        let limits = layout::Limits::new(Size::ZERO, self.size)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let mut child = self.content.as_widget().layout(renderer, &limits);
+        child.align(Alignment::Center, Alignment::Center, limits.max());
+
+        let mut node = layout::Node::with_children(self.size, vec![child]);
+        node.move_to(position);
+        node
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let content = layout.children().next().expect("modal has a dialog layout");
+
+        if let Some(message) = self.on_blur.as_ref() {
+            if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = &event {
+                if !cursor.is_over(content.bounds()) {
+                    shell.publish(message.clone());
+                    return event::Status::Captured;
+                }
+            }
+        }
+
+        self.content.as_widget_mut().on_event(
+            self.tree,
+            event,
+            content,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            &layout.bounds(),
+        )
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Renderer::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+    ) {
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: layout.bounds(),
+                border_radius: Default::default(),
+                border_width: 0.0,
+                border_color: Color::TRANSPARENT,
+            },
+            Color::from_rgba(0.0, 0.0, 0.0, self.dim),
+        );
+
+        self.content.as_widget().draw(
+            self.tree,
+            renderer,
+            theme,
+            style,
+            layout.children().next().expect("modal has a dialog layout"),
+            cursor,
+            &layout.bounds(),
+        );
+    }
+
+    fn operate(
+        &mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.content.as_widget().operate(
+            self.tree,
+            layout.children().next().expect("modal has a dialog layout"),
+            renderer,
+            operation,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            self.tree,
+            layout.children().next().expect("modal has a dialog layout"),
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+}
+
+impl<'a, Message, Renderer> From<Modal<'a, Message, Renderer>> for Element<'a, Message, Renderer>
+where
+    Renderer: 'a + advanced::Renderer,
+    Message: 'a + Clone,
+{
+    fn from(modal: Modal<'a, Message, Renderer>) -> Self {
+        Element::new(modal)
+    }
+}
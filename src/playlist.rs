@@ -0,0 +1,87 @@
+// src/playlist.rs
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::data_types::DataSource;
+
+const DEFAULT_DWELL_SECS: u64 = 30;
+const MIN_DWELL_SECS: u64 = 1;
+
+fn default_dwell() -> u64 {
+    DEFAULT_DWELL_SECS
+}
+
+// One source in the signage rotation, e.g. rifle results shown for 30 seconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlaylistEntry {
+    pub source: DataSource,
+    #[serde(default = "default_dwell")]
+    pub dwell_secs: u64,
+    #[serde(default)]
+    pub title: Option<String>,  // Banner title while this source is showing
+}
+
+impl PlaylistEntry {
+    pub fn dwell(&self) -> Duration {
+        Duration::from_secs(self.dwell_secs.max(MIN_DWELL_SECS))
+    }
+}
+
+// Cycles through the configured sources, each for its own dwell time
+#[derive(Debug, Clone)]
+pub struct Playlist {
+    entries: Vec<PlaylistEntry>,
+    index: usize,
+    paused: bool,
+    switched_at: Instant,
+}
+
+impl Playlist {
+    pub fn new(entries: Vec<PlaylistEntry>) -> Self {
+        Playlist {
+            entries,
+            index: 0,
+            paused: false,
+            switched_at: Instant::now(),
+        }
+    }
+
+    // A single entry is just a fixed source, so only longer lists rotate
+    pub fn rotates(&self) -> bool {
+        self.entries.len() > 1
+    }
+
+    pub fn current(&self) -> Option<&PlaylistEntry> {
+        self.entries.get(self.index)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    // Whether the current source has been shown for its full dwell time
+    pub fn is_due(&self) -> bool {
+        self.rotates() && !self.paused
+            && self.current().map_or(false, |entry| self.switched_at.elapsed() >= entry.dwell())
+    }
+
+    // Move on to the next source, wrapping around at the end
+    pub fn advance(&mut self) -> Option<&PlaylistEntry> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        self.index = (self.index + 1) % self.entries.len();
+        self.switched_at = Instant::now();
+        self.current()
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    // Resuming gives the current source a full dwell again
+    pub fn toggle_paused(&mut self) {
+        self.paused = !self.paused;
+        self.switched_at = Instant::now();
+    }
+}
@@ -1,7 +1,12 @@
 // src/ui.rs
 use iced::Color;
 use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
 
+#[derive(Debug, Clone)]
 pub struct Styles {
     pub bg: Color,
     pub fg: Color,
@@ -9,22 +14,184 @@ pub struct Styles {
     pub footer_fg: Color,
     pub header_bg: Color,
     pub header_fg: Color,
+    pub error_bg: Color,
+    pub error_fg: Color,
+    pub result_bg: Color,
+    pub result_fg: Color,
+    pub gold: Color,
+    pub silver: Color,
+    pub bronze: Color,
+    pub changed: Color,  // Flash behind rows whose results just changed
+    pub added: Color,    // Flash behind rows new since the last refresh
+    pub selected: Color, // Row clicked by the operator
+    pub stripe: Color,   // Every other row when striping is on
+    pub clock_bg: Color,
+    pub clock_fg: Color,
+    pub valid: Color,    // Check mark beside a recognized input
+    pub invalid: Color,  // Cross beside an input that won't work
 }
 
 pub static DARK_THEME: Lazy<Styles> = Lazy::new(|| Styles {
-    bg: Color::from_rgb(0.0, 0.0, 0.0),
-    fg: Color::from_rgb(1.0, 1.0, 1.0),
-    footer_bg: Color::from_rgb(0.0078, 0.325, 0.6118), // #02539c
-    footer_fg: Color::from_rgb(1.0, 1.0, 1.0),
-    header_bg: Color::from_rgb(0.2, 0.2, 0.2),
-    header_fg: Color::from_rgb(1.0, 1.0, 1.0),
+    bg: hex("#000000"),
+    fg: hex("#ffffff"),
+    footer_bg: hex("#02539c"),
+    footer_fg: hex("#ffffff"),
+    header_bg: hex("#333333"),
+    header_fg: hex("#ffffff"),
+    error_bg: hex("#991a1a"),
+    error_fg: hex("#ffffff"),
+    result_bg: hex("#002850"),
+    result_fg: hex("#ffd700"),
+    gold: hex("#7a6200"),
+    silver: hex("#5a5a5a"),
+    bronze: hex("#6e3d14"),
+    changed: hex("#1e6b2e"),
+    added: hex("#1e4f8a"),
+    selected: hex("#4a4a7a"),
+    stripe: hex("#141414"),
+    clock_bg: hex("#1a1a1a"),
+    clock_fg: hex("#ffd700"),
+    valid: hex("#4cd964"),
+    invalid: hex("#ff5c5c"),
 });
 
 pub static LIGHT_THEME: Lazy<Styles> = Lazy::new(|| Styles {
-    bg: Color::from_rgb(1.0, 1.0, 1.0),
-    fg: Color::from_rgb(0.0, 0.0, 0.0),
-    footer_bg: Color::from_rgb(0.0078, 0.325, 0.6118), // #02539c
-    footer_fg: Color::from_rgb(1.0, 1.0, 1.0),
-    header_bg: Color::from_rgb(0.8784, 0.8784, 0.8784), // #e0e0e0
-    header_fg: Color::from_rgb(0.0, 0.0, 0.0),
+    bg: hex("#ffffff"),
+    fg: hex("#000000"),
+    footer_bg: hex("#02539c"),
+    footer_fg: hex("#ffffff"),
+    header_bg: hex("#e0e0e0"),
+    header_fg: hex("#000000"),
+    error_bg: hex("#f8d7da"),
+    error_fg: hex("#721c24"),
+    result_bg: hex("#dbeafe"),
+    result_fg: hex("#02539c"),
+    gold: hex("#ffd700"),
+    silver: hex("#c0c0c0"),
+    bronze: hex("#cd7f32"),
+    changed: hex("#b7f0c4"),
+    added: hex("#b8d8ff"),
+    selected: hex("#fff1a8"),
+    stripe: hex("#f2f2f2"),
+    clock_bg: hex("#02539c"),
+    clock_fg: hex("#ffffff"),
+    valid: hex("#1e7b34"),
+    invalid: hex("#c62828"),
 });
+
+impl Styles {
+    // Load a theme from a TOML or JSON file of hex colors, e.g. `bg = "#02539c"`.
+    // Colors missing from the file keep their dark theme value.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Styles, Box<dyn Error>> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        
+        let colors: HashMap<String, String> = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&contents)?,
+            _ => toml::from_str(&contents)?,
+        };
+        
+        let mut styles = DARK_THEME.clone();
+        for (name, value) in colors.iter() {
+            let color = parse_hex(value)
+                .ok_or_else(|| format!("invalid color for '{}': '{}'", name, value))?;
+            styles.set_color(name, color)?;
+        }
+        
+        Ok(styles)
+    }
+
+    fn set_color(&mut self, name: &str, color: Color) -> Result<(), Box<dyn Error>> {
+        let field = match name {
+            "bg" => &mut self.bg,
+            "fg" => &mut self.fg,
+            "footer_bg" => &mut self.footer_bg,
+            "footer_fg" => &mut self.footer_fg,
+            "header_bg" => &mut self.header_bg,
+            "header_fg" => &mut self.header_fg,
+            "error_bg" => &mut self.error_bg,
+            "error_fg" => &mut self.error_fg,
+            "result_bg" => &mut self.result_bg,
+            "result_fg" => &mut self.result_fg,
+            "gold" => &mut self.gold,
+            "silver" => &mut self.silver,
+            "bronze" => &mut self.bronze,
+            "changed" => &mut self.changed,
+            "added" => &mut self.added,
+            "selected" => &mut self.selected,
+            "stripe" => &mut self.stripe,
+            "clock_bg" => &mut self.clock_bg,
+            "clock_fg" => &mut self.clock_fg,
+            "valid" => &mut self.valid,
+            "invalid" => &mut self.invalid,
+            _ => return Err(format!("unknown theme color '{}'", name).into()),
+        };
+        *field = color;
+        Ok(())
+    }
+}
+
+// Color from a hex literal, for the built-in themes
+fn hex(s: &str) -> Color {
+    parse_hex(s).unwrap_or_else(|| panic!("invalid built-in color '{}'", s))
+}
+
+// Parse "#rrggbb", "#rrggbbaa" or shorthand "#rgb" into a Color
+pub fn parse_hex(s: &str) -> Option<Color> {
+    let digits = s.trim().strip_prefix('#')?;
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    
+    // Expand shorthand by doubling each digit
+    let digits: String = match digits.len() {
+        3 => digits.chars().flat_map(|c| [c, c]).collect(),
+        6 | 8 => digits.to_string(),
+        _ => return None,
+    };
+    
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    let alpha = if digits.len() == 8 { channel(6)? } else { 255 };
+    
+    Some(Color::from_rgba8(channel(0)?, channel(2)?, channel(4)?, alpha as f32 / 255.0))
+}
+
+// Blend from one color towards another, `amount` going from 0.0 to 1.0
+pub fn mix(from: Color, to: Color, amount: f32) -> Color {
+    let amount = amount.clamp(0.0, 1.0);
+    Color {
+        r: from.r + (to.r - from.r) * amount,
+        g: from.g + (to.g - from.g) * amount,
+        b: from.b + (to.b - from.b) * amount,
+        a: from.a + (to.a - from.a) * amount,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rrggbb() {
+        assert_eq!(parse_hex("#02539c"), Some(Color::from_rgb8(0x02, 0x53, 0x9c)));
+        assert_eq!(parse_hex(" #02539C "), Some(Color::from_rgb8(0x02, 0x53, 0x9c)));
+    }
+
+    #[test]
+    fn parses_shorthand() {
+        assert_eq!(parse_hex("#fff"), Some(Color::from_rgb8(0xff, 0xff, 0xff)));
+        assert_eq!(parse_hex("#08c"), Some(Color::from_rgb8(0x00, 0x88, 0xcc)));
+    }
+
+    #[test]
+    fn parses_rrggbbaa() {
+        assert_eq!(parse_hex("#02539c80"), Some(Color::from_rgba8(0x02, 0x53, 0x9c, 128.0 / 255.0)));
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        for input in ["", "#", "02539c", "#02539", "#02539c8", "#zzzzzz", "#0253 9c"] {
+            assert_eq!(parse_hex(input), None, "{:?}", input);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// src/url_handler.rs
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use std::error::Error;
+use std::time::Duration;
+
+use crate::columns::ColumnConfig;
+use crate::csv_handler::CSVHandler;
+use crate::data_types::TableData;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// HTTP caching validators from the last successful download
+#[derive(Debug, Clone, Default)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum UrlFetch {
+    Updated(TableData, Validators),
+    NotModified,
+}
+
+pub struct UrlHandler {
+    columns: ColumnConfig,
+    timeout: Duration,
+}
+
+impl UrlHandler {
+    pub fn new(columns: ColumnConfig) -> Self {
+        UrlHandler {
+            columns,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    // Download a CSV file, skipping the body when the server says it hasn't changed
+    pub async fn fetch(&self, url: &str, validators: &Validators) -> Result<UrlFetch, Box<dyn Error>> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(self.timeout)
+            .timeout(self.timeout)
+            .build()?;
+        
+        let mut request = client.get(url);
+        if let Some(ref etag) = validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(ref last_modified) = validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        
+        log::debug!("Downloading {}", url);
+        let response = request.send().await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(UrlFetch::NotModified);
+        }
+        let response = response.error_for_status()?;
+        
+        let header = |name| response.headers()
+            .get(name)
+            .and_then(|value: &reqwest::header::HeaderValue| value.to_str().ok())
+            .map(String::from);
+        let validators = Validators {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        };
+        
+        let bytes = response.bytes().await?;
+        let data = CSVHandler::parse_bytes(&bytes, &self.columns)?;
+        
+        Ok(UrlFetch::Updated(data, validators))
+    }
+}
@@ -0,0 +1,182 @@
+// src/value.rs
+
+// Trim a cell and collapse inner runs of whitespace, non-breaking spaces included
+pub fn normalize_whitespace(cell: &str) -> String {
+    cell.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+// Round to a fixed number of decimals, optionally grouping thousands, e.g.
+// 612.3000000001 with 1 decimal gives "612.3" and 12345.0 with "," gives "12,345"
+pub fn format_number(value: f64, decimals: usize, thousands: Option<&str>) -> String {
+    let formatted = format!("{:.*}", decimals, value);
+    let separator = match thousands {
+        Some(separator) if !separator.is_empty() => separator,
+        _ => return formatted,
+    };
+    
+    let (sign, unsigned) = match formatted.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", formatted.as_str()),
+    };
+    let (whole, fraction) = match unsigned.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (unsigned, None),
+    };
+    
+    let digits: Vec<char> = whole.chars().collect();
+    let mut grouped = String::new();
+    for (i, digit) in digits.iter().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push_str(separator);
+        }
+        grouped.push(*digit);
+    }
+    
+    match fraction {
+        Some(fraction) => format!("{}{}.{}", sign, grouped, fraction),
+        None => format!("{}{}", sign, grouped),
+    }
+}
+
+// Shorten a cell to at most `max_chars` characters, ending in "…" when `ellipsis` is set.
+// None when it already fits.
+pub fn truncate(cell: &str, max_chars: usize, ellipsis: bool) -> Option<String> {
+    if cell.chars().count() <= max_chars {
+        return None;
+    }
+    
+    if ellipsis {
+        let mut short: String = cell.chars().take(max_chars.saturating_sub(1)).collect();
+        short.push('…');
+        Some(short)
+    } else {
+        Some(cell.chars().take(max_chars).collect())
+    }
+}
+
+// Parse a shooting time such as "9:59.9", "10:05.32" or "1:02:03.4" into total seconds.
+// Minutes and seconds after the first field must be two digits below 60.
+pub fn parse_time(cell: &str) -> Option<f64> {
+    let cell = cell.trim();
+    let mut fields = cell.split(':');
+    let first = fields.next()?;
+    let rest: Vec<&str> = fields.collect();
+    
+    // Either mm:ss.s or h:mm:ss.s
+    if rest.is_empty() || rest.len() > 2 {
+        return None;
+    }
+    
+    let mut total = parse_whole(first)? as f64;
+    
+    for (i, field) in rest.iter().enumerate() {
+        let is_seconds = i == rest.len() - 1;
+        let value = if is_seconds { parse_seconds(field)? } else { parse_whole(field)? as f64 };
+        
+        if field.split(['.', ',']).next()?.len() != 2 || value >= 60.0 {
+            return None;
+        }
+        
+        total = total * 60.0 + value;
+    }
+    
+    Some(total)
+}
+
+// Format seconds back into a shooting time, "m:ss.s" or "h:mm:ss.s" past an hour
+pub fn format_time(seconds: f64) -> String {
+    let tenths = (seconds * 10.0).round() as u64;
+    let (hours, minutes, secs) = (tenths / 36000, tenths / 600 % 60, tenths % 600);
+    
+    if hours > 0 {
+        format!("{}:{:02}:{:02}.{}", hours, minutes, secs / 10, secs % 10)
+    } else {
+        format!("{}:{:02}.{}", minutes, secs / 10, secs % 10)
+    }
+}
+
+fn parse_whole(field: &str) -> Option<u32> {
+    if field.is_empty() || !field.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    field.parse().ok()
+}
+
+// Seconds with an optional fraction after a decimal point or comma
+fn parse_seconds(field: &str) -> Option<f64> {
+    let (whole, fraction) = match field.split_once(['.', ',']) {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (field, None),
+    };
+    
+    let whole = parse_whole(whole)?;
+    let fraction = match fraction {
+        Some(digits) => {
+            parse_whole(digits)?;
+            format!("0.{}", digits).parse::<f64>().ok()?
+        }
+        None => 0.0,
+    };
+    
+    Some(whole as f64 + fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Option<f64>, b: f64) -> bool {
+        a.map_or(false, |a| (a - b).abs() < 1e-9)
+    }
+
+    #[test]
+    fn parses_times() {
+        assert!(close(parse_time("9:59.9"), 599.9));
+        assert!(close(parse_time("10:00.0"), 600.0));
+        assert!(close(parse_time("10:05,32"), 605.32));
+        assert!(close(parse_time("1:02:03.4"), 3723.4));
+        assert!(parse_time("9:59.9") < parse_time("10:00.0"));
+    }
+
+    #[test]
+    fn rejects_malformed_times() {
+        for cell in ["", "DNF", "9", "9:5.0", "9:60.0", "1:2:03.4", "1:02:03:04.0", "-1:00.0", "10:00.x", "10:"] {
+            assert_eq!(parse_time(cell), None, "{:?}", cell);
+        }
+    }
+
+    #[test]
+    fn formats_times() {
+        assert_eq!(format_time(599.9), "9:59.9");
+        assert_eq!(format_time(3723.4), "1:02:03.4");
+    }
+
+    #[test]
+    fn normalizes_whitespace() {
+        assert_eq!(normalize_whitespace("  Team   A \u{00A0}"), "Team A");
+        assert_eq!(normalize_whitespace("Laine\u{00A0}\u{00A0}Eero"), "Laine Eero");
+        assert_eq!(normalize_whitespace(" \t "), "");
+    }
+
+    #[test]
+    fn rounds_to_the_configured_decimals() {
+        assert_eq!(format_number(612.3000000001, 1, None), "612.3");
+        assert_eq!(format_number(9.96, 1, None), "10.0");
+        assert_eq!(format_number(3.4, 0, None), "3");
+    }
+
+    #[test]
+    fn keeps_trailing_zeros() {
+        assert_eq!(format_number(2.0, 2, None), "2.00");
+        assert_eq!(format_number(98.5, 3, None), "98.500");
+    }
+
+    #[test]
+    fn groups_thousands() {
+        assert_eq!(format_number(12345.0, 0, Some(",")), "12,345");
+        assert_eq!(format_number(-1234567.891, 2, Some(" ")), "-1 234 567.89");
+        assert_eq!(format_number(999.96, 1, Some(",")), "1,000.0");
+        assert_eq!(format_number(123.0, 0, Some(",")), "123");
+        assert_eq!(format_number(12345.0, 0, Some("")), "12345");
+    }
+}
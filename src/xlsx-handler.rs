@@ -0,0 +1,89 @@
+// src/xlsx_handler.rs
+use std::path::Path;
+use calamine::{open_workbook_auto, DataType, Reader};
+use std::fmt;
+use std::error::Error;
+use tokio::task;
+
+use crate::cloud_handler::process_values;
+use crate::columns::ColumnConfig;
+use crate::data_types::TableData;
+
+#[derive(Debug)]
+pub enum XlsxError {
+    Open(calamine::Error),
+    MissingSheet(String),
+    EmptySheet,
+}
+
+impl fmt::Display for XlsxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            XlsxError::Open(err) => write!(f, "Could not read workbook: {}", err),
+            XlsxError::MissingSheet(name) => write!(f, "The workbook has no sheet named \"{}\"", name),
+            XlsxError::EmptySheet => write!(f, "The worksheet is empty"),
+        }
+    }
+}
+
+impl Error for XlsxError {}
+
+impl From<calamine::Error> for XlsxError {
+    fn from(err: calamine::Error) -> Self {
+        XlsxError::Open(err)
+    }
+}
+
+pub struct XlsxHandler {
+    columns: ColumnConfig,
+    sheet: Option<String>,
+}
+
+impl XlsxHandler {
+    pub fn new(columns: ColumnConfig) -> Self {
+        XlsxHandler { columns, sheet: None }
+    }
+
+    // Read a named worksheet instead of the first one
+    pub fn with_sheet(mut self, sheet: String) -> Self {
+        self.sheet = Some(sheet);
+        self
+    }
+
+    pub async fn read_xlsx<P: AsRef<Path> + Send + 'static>(&self, path: P) -> Result<TableData, XlsxError> {
+        let columns = self.columns.clone();
+        let sheet = self.sheet.clone();
+        
+        task::spawn_blocking(move || {
+            log::info!("Reading {}", path.as_ref().display());
+            let mut workbook = open_workbook_auto(&path)?;
+            
+            let name = match sheet {
+                Some(name) => name,
+                None => workbook.sheet_names().first().cloned().ok_or(XlsxError::EmptySheet)?,
+            };
+            let range = workbook.worksheet_range(&name)
+                .ok_or_else(|| XlsxError::MissingSheet(name.clone()))??;
+            
+            let values: Vec<Vec<String>> = range.rows()
+                .map(|row| row.iter().map(Self::cell_to_string).collect())
+                .collect();
+            
+            // Title rows, header markers and mappings work as in Google Sheets
+            let data = process_values(values, &columns);
+            if data.headers.iter().all(|header| header.trim().is_empty()) {
+                return Err(XlsxError::EmptySheet);
+            }
+            
+            Ok(data)
+        }).await.unwrap_or_else(|err| Err(XlsxError::Open(calamine::Error::Io(std::io::Error::new(std::io::ErrorKind::Other, err)))))
+    }
+
+    fn cell_to_string(cell: &DataType) -> String {
+        match cell {
+            DataType::Empty => String::new(),
+            DataType::String(s) => s.clone(),
+            other => other.to_string(),
+        }
+    }
+}